@@ -0,0 +1,70 @@
+//! Measures how the scheduler service scales with the number of scheduled jobs.
+//!
+//! Run with `cargo bench --bench scaling`.
+
+use async_cron_scheduler::{cron, Job, MockClock, Scheduler};
+use chrono::{TimeZone, Utc};
+use futures::executor::LocalPool;
+use futures::task::LocalSpawnExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn main() {
+    for jobs in [1_000, 10_000, 100_000] {
+        bench(jobs);
+    }
+}
+
+fn bench(jobs: usize) {
+    let clock = MockClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+    let (scheduler, service) = Scheduler::launch(Utc, clock.clone());
+
+    let mut pool = LocalPool::new();
+    pool.spawner().spawn_local(service).unwrap();
+
+    // Spread the jobs across every second of the minute.
+    let schedules = (0..60)
+        .map(|second| {
+            format!("{second} * * * * *")
+                .parse::<cron::Schedule>()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let fired = Arc::new(AtomicUsize::new(0));
+
+    let start = Instant::now();
+    pool.run_until(async {
+        for job in 0..jobs {
+            let job = Job::cron_schedule(schedules[job % 60].clone());
+            let fired = fired.clone();
+            scheduler
+                .insert(job, move |_| {
+                    fired.fetch_add(1, Ordering::Relaxed);
+                })
+                .await
+                .unwrap();
+        }
+    });
+    pool.run_until_stalled();
+    let inserting = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..60 {
+        clock.advance(Duration::from_secs(1));
+        pool.run_until_stalled();
+    }
+    let firing = start.elapsed();
+
+    let fired = fired.load(Ordering::Relaxed);
+    assert_eq!(fired, jobs);
+
+    println!(
+        "{jobs:>7} jobs: insert {:>8.0?} ({:>6.0?}/job), fire {:>8.0?} ({:>6.0?}/event)",
+        inserting,
+        inserting / jobs as u32,
+        firing,
+        firing / fired as u32,
+    );
+}
@@ -9,30 +9,30 @@ fn main() {
         // Creates a scheduler based on the Local timezone. Note that the `sched_service`
         // contains the background job as a future for the caller to decide how to await
         // it. When the scheduler is dropped, the scheduler service will exit as well.
-        let (mut scheduler, sched_service) = Scheduler::<Local>::launch(Timer::after);
+        let (scheduler, sched_service) = Scheduler::launch(Local, Timer::after);
 
         // Creates a job which executes every 1 seconds.
         let job = Job::cron("1/1 * * * * *").unwrap();
-        let fizz_id = scheduler.insert(job, |_id| println!("Fizz")).await;
+        let fizz_id = scheduler.insert(job, |_id| println!("Fizz")).await.unwrap();
 
         // Creates a job which executes every 3 seconds.
         let job = Job::cron("1/3 * * * * *").unwrap();
-        let buzz_id = scheduler.insert(job, |_id| println!("Buzz")).await;
+        let buzz_id = scheduler.insert(job, |_id| println!("Buzz")).await.unwrap();
 
         // Creates a job which executes every 5 seconds.
         let job = Job::cron("1/5 * * * * *").unwrap();
-        let bazz_id = scheduler.insert(job, |_id| println!("Bazz")).await;
+        let bazz_id = scheduler.insert(job, |_id| println!("Bazz")).await.unwrap();
 
         // A future which gradually drops jobs from the scheduler.
         let dropper = async move {
             Timer::after(Duration::from_secs(7)).await;
-            scheduler.remove(fizz_id).await;
+            scheduler.remove(fizz_id).await.unwrap();
             println!("Fizz gone");
             Timer::after(Duration::from_secs(5)).await;
-            scheduler.remove(buzz_id).await;
+            scheduler.remove(buzz_id).await.unwrap();
             println!("Buzz gone");
             Timer::after(Duration::from_secs(1)).await;
-            scheduler.remove(bazz_id).await;
+            scheduler.remove(bazz_id).await.unwrap();
             println!("Bazz gone");
 
             // `scheduler` is dropped here, which causes the sched_service to end.
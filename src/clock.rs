@@ -0,0 +1,172 @@
+// Copyright 2021 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use chrono::{DateTime, Utc};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// The source of time used by the scheduler service.
+///
+/// Any `Fn(Duration) -> impl Future` timer function, such as `smol::Timer::after`
+/// or `tokio::time::sleep`, is a clock which reads the time from the system.
+pub trait Clock: Send + Sync + 'static {
+    /// The future returned when sleeping.
+    type Sleep: Future + Send;
+
+    /// Get the current time.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Sleep until the given time has been reached.
+    ///
+    /// Like most timers, the sleep may measure the time elapsed rather than
+    /// watch the wall clock, so it can finish at the wrong time if the wall
+    /// clock changes or the system is suspended while sleeping.
+    fn sleep_until(&self, deadline: DateTime<Utc>) -> Self::Sleep;
+
+    /// Get the current monotonic time, used to detect changes to the wall clock.
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+impl<T, F> Clock for T
+where
+    T: Fn(Duration) -> F + Send + Sync + 'static,
+    F: Future + Send,
+{
+    type Sleep = F;
+
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep_until(&self, deadline: DateTime<Utc>) -> Self::Sleep {
+        // Negative durations fail to convert, which means the deadline has passed.
+        let duration = deadline
+            .signed_duration_since(Utc::now())
+            .to_std()
+            .unwrap_or_default();
+
+        self(duration)
+    }
+}
+
+/// A clock which only moves when told to, for deterministic testing.
+///
+/// Clones share the same time, so a test can keep one copy to advance while
+/// the scheduler service sleeps on another. Like a real timer, sleeps track
+/// the time elapsed with [`MockClock::advance`], so changing the wall clock
+/// with [`MockClock::set`] does not wake them.
+///
+/// ```
+/// use async_cron_scheduler::{Clock, MockClock};
+/// use chrono::{TimeZone, Utc};
+/// use std::time::Duration;
+///
+/// let clock = MockClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+/// clock.advance(Duration::from_secs(90));
+/// assert_eq!(clock.now(), Utc.with_ymd_and_hms(2024, 1, 1, 0, 1, 30).unwrap());
+/// ```
+#[derive(Clone)]
+pub struct MockClock {
+    inner: Arc<Mutex<MockState>>,
+}
+
+struct MockState {
+    now: DateTime<Utc>,
+    origin: Instant,
+    elapsed: Duration,
+    sleepers: Vec<Waker>,
+}
+
+impl MockClock {
+    /// Creates a clock which starts at the given time.
+    #[must_use]
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(MockState {
+                now,
+                origin: Instant::now(),
+                elapsed: Duration::ZERO,
+                sleepers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Lets time pass, waking any sleeps whose deadline may have passed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the duration is out of range for a `DateTime`.
+    pub fn advance(&self, duration: Duration) {
+        let sleepers = {
+            let mut state = self.inner.lock().unwrap();
+            state.now += chrono::Duration::from_std(duration).unwrap();
+            state.elapsed += duration;
+            std::mem::take(&mut state.sleepers)
+        };
+
+        for waker in sleepers {
+            waker.wake();
+        }
+    }
+
+    /// Changes the wall clock to the given time without letting any time pass.
+    ///
+    /// This is how the clock appears to a sleeping process when the system
+    /// time is changed, or when the system resumes from suspend.
+    pub fn set(&self, time: DateTime<Utc>) {
+        self.inner.lock().unwrap().now = time;
+    }
+}
+
+impl Clock for MockClock {
+    type Sleep = MockSleep;
+
+    fn now(&self) -> DateTime<Utc> {
+        self.inner.lock().unwrap().now
+    }
+
+    fn sleep_until(&self, deadline: DateTime<Utc>) -> Self::Sleep {
+        let state = self.inner.lock().unwrap();
+
+        let duration = deadline
+            .signed_duration_since(state.now)
+            .to_std()
+            .unwrap_or_default();
+
+        MockSleep {
+            clock: self.clone(),
+            until: state.elapsed + duration,
+        }
+    }
+
+    fn instant(&self) -> Instant {
+        let state = self.inner.lock().unwrap();
+        state.origin + state.elapsed
+    }
+}
+
+/// A sleep which completes once enough time has passed on its [`MockClock`].
+pub struct MockSleep {
+    clock: MockClock,
+    until: Duration,
+}
+
+impl Future for MockSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.clock.inner.lock().unwrap();
+
+        if state.elapsed >= self.until {
+            return Poll::Ready(());
+        }
+
+        state.sleepers.push(cx.waker().clone());
+        Poll::Pending
+    }
+}
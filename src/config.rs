@@ -0,0 +1,75 @@
+// Copyright 2021 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Options for launching a scheduler with [`crate::Scheduler::launch_with`].
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Default)]
+pub struct SchedulerConfig {
+    pub(crate) resync: Option<Duration>,
+    pub(crate) max_concurrency: Option<usize>,
+    pub(crate) pools: HashMap<String, usize>,
+}
+
+impl SchedulerConfig {
+    /// Wakes the service at least this often while waiting for the next job.
+    ///
+    /// Timers measure elapsed time rather than the wall clock, so a sleep can
+    /// overshoot its job if the system clock jumps forward or the system is
+    /// suspended. The service checks for such jumps each time it wakes, so a
+    /// shorter interval bounds how late a job fires after one.
+    #[must_use]
+    pub fn resync(mut self, interval: Duration) -> Self {
+        self.resync = Some(interval);
+        self
+    }
+
+    /// Limits how many async commands may run at once across every job.
+    ///
+    /// Occurrences which fire while the limit is reached are deferred, in the
+    /// order that they fired, until a running command completes. Commands
+    /// which are not async run as soon as they fire, so they are not limited.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Scheduler, SchedulerConfig};
+    /// # use chrono::Local;
+    /// let config = SchedulerConfig::default().max_concurrency(8);
+    /// let (scheduler, sched_service) = Scheduler::launch_with(Local, smol::Timer::after, config);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the limit is zero, as no async command could ever run.
+    #[must_use]
+    pub fn max_concurrency(mut self, limit: usize) -> Self {
+        assert!(limit > 0, "concurrency limit must be greater than zero");
+        self.max_concurrency = Some(limit);
+        self
+    }
+
+    /// Limits how many async commands of jobs assigned to the named pool may run at once.
+    ///
+    /// Jobs are assigned to a pool with [`crate::Job::pool`]. Pools are limited
+    /// in addition to the limit set by [`SchedulerConfig::max_concurrency`].
+    /// Pools which are not configured here have no limit of their own.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler, SchedulerConfig};
+    /// # use chrono::Local;
+    /// // At most two jobs may query the database at once.
+    /// let config = SchedulerConfig::default().pool("database", 2);
+    /// let job = Job::<Local>::cron("0 * * * * *").unwrap().pool("database");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the limit is zero, as no async command in the pool could ever run.
+    #[must_use]
+    pub fn pool(mut self, name: impl Into<String>, limit: usize) -> Self {
+        assert!(limit > 0, "pool limit must be greater than zero");
+        self.pools.insert(name.into(), limit);
+        self
+    }
+}
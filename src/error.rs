@@ -0,0 +1,26 @@
+// Copyright 2021 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use std::fmt;
+
+/// Errors returned when the scheduler cannot carry out a request.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SchedulerError {
+    /// The scheduler service has exited, either because its future was
+    /// dropped or because it panicked.
+    ServiceStopped,
+    /// The job is not scheduled, as it was removed or has no occurrences left.
+    UnknownJob,
+}
+
+impl fmt::Display for SchedulerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchedulerError::ServiceStopped => f.write_str("scheduler service has stopped"),
+            SchedulerError::UnknownJob => f.write_str("job is not scheduled"),
+        }
+    }
+}
+
+impl std::error::Error for SchedulerError {}
@@ -0,0 +1,69 @@
+// Copyright 2021 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{JobId, TimeZoneExt};
+use chrono::DateTime;
+use futures::channel::mpsc::UnboundedSender;
+
+/// Activity of the scheduler service, observed with [`crate::Scheduler::subscribe`].
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchedulerEvent<Tz: TimeZoneExt> {
+    /// A job was inserted.
+    Inserted(JobId),
+    /// A job was removed from the scheduler.
+    Removed(JobId),
+    /// A job's command was called.
+    Fired {
+        /// The ID of the job.
+        id: JobId,
+        /// When the occurrence was scheduled.
+        scheduled: DateTime<Tz>,
+        /// When the command was called.
+        actual: DateTime<Tz>,
+    },
+    /// A job's command finished running.
+    ///
+    /// Commands which are not async complete as soon as they are fired.
+    Completed(JobId),
+    /// A run of a job's async command was cancelled before it completed, by
+    /// [`crate::Scheduler::cancel_running`] or the job's [`crate::OverlapPolicy`].
+    Cancelled(JobId),
+    /// A run of a job's async command was cancelled, because it did not
+    /// complete within the job's [`crate::Job::timeout`].
+    TimedOut(JobId),
+    /// A job had no occurrences left, so it was removed from the scheduler.
+    Exhausted(JobId),
+    /// An occurrence was skipped, because it was missed as permitted by the job's
+    /// [`crate::MisfirePolicy`], or its previous run was still running as
+    /// permitted by its [`crate::OverlapPolicy`].
+    ///
+    /// Each occurrence is reported separately, even when several were missed at once.
+    Skipped {
+        /// The ID of the job.
+        id: JobId,
+        /// When the occurrence was scheduled.
+        scheduled: DateTime<Tz>,
+    },
+}
+
+/// Senders to every subscriber of the scheduler's events.
+pub(crate) struct Subscribers<Tz: TimeZoneExt>(Vec<UnboundedSender<SchedulerEvent<Tz>>>);
+
+impl<Tz: TimeZoneExt> Default for Subscribers<Tz> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<Tz: TimeZoneExt> Subscribers<Tz> {
+    pub fn add(&mut self, sender: UnboundedSender<SchedulerEvent<Tz>>) {
+        self.0.push(sender);
+    }
+
+    /// Sends the event to every subscriber, forgetting those which have been dropped.
+    pub fn emit(&mut self, event: SchedulerEvent<Tz>) {
+        self.0
+            .retain(|subscriber| subscriber.unbounded_send(event.clone()).is_ok());
+    }
+}
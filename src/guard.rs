@@ -0,0 +1,46 @@
+// Copyright 2021 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::scheduler::JobRemover;
+use crate::JobId;
+
+/// Removes a job from the scheduler when dropped, created with [`crate::Scheduler::insert_guarded`].
+///
+/// Removal does not wait for the scheduler service, so guards may be dropped
+/// outside of an async context.
+#[allow(clippy::module_name_repetitions)]
+#[must_use = "the job is removed as soon as the guard is dropped"]
+pub struct JobGuard {
+    id: JobId,
+    remover: Option<JobRemover>,
+}
+
+impl JobGuard {
+    pub(crate) fn new(id: JobId, remover: JobRemover) -> Self {
+        Self {
+            id,
+            remover: Some(remover),
+        }
+    }
+
+    /// The ID of the guarded job.
+    #[must_use]
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// Releases the job from the guard, so that it stays scheduled until removed by ID.
+    #[must_use]
+    pub fn detach(mut self) -> JobId {
+        self.remover = None;
+        self.id
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        if let Some(remover) = &self.remover {
+            remover.remove(self.id);
+        }
+    }
+}
@@ -1,22 +1,116 @@
 // Copyright 2021 System76 <info@system76.com>
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::TimeZoneExt;
+use crate::schedule::{add, cron_next_after, After, At, Every};
+use crate::{DstPolicy, Schedule, TimeZoneExt};
 use chrono::DateTime;
 use std::str::FromStr;
+use std::time::Duration;
 
 /// The ID of a scheduled job.
 #[allow(clippy::module_name_repetitions)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct JobId(pub slotmap::DefaultKey);
 
+/// Describes a job which is scheduled in the scheduler service.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug)]
+pub struct JobInfo<Tz: TimeZoneExt> {
+    /// The ID of the job.
+    pub id: JobId,
+    /// The name given to the job with [`Job::name`].
+    pub name: Option<String>,
+    /// When the job will next fire, or would have fired if it were not paused.
+    pub next: DateTime<Tz>,
+    /// Whether the job is paused.
+    pub paused: bool,
+}
+
+/// How a job handles occurrences which were missed.
+///
+/// An occurrence is missed when the service fires it more than
+/// [`MisfirePolicy::THRESHOLD`] after it was due, such as when the system was
+/// suspended or the service was starved.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MisfirePolicy {
+    /// Fire every missed occurrence.
+    #[default]
+    FireAll,
+    /// Fire once for all missed occurrences, then continue from the next occurrence after now.
+    FireOnce,
+    /// Skip all missed occurrences, continuing from the next occurrence after now.
+    Skip,
+    /// Fire missed occurrences which are no later than the grace period, skipping the rest.
+    Grace(Duration),
+}
+
+impl MisfirePolicy {
+    /// How late an occurrence may fire before it is considered missed.
+    pub const THRESHOLD: Duration = Duration::from_secs(1);
+}
+
+/// How a job with an async command handles occurrences which fire while a
+/// previous run of its command is still running.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Run the command again, alongside any previous runs.
+    #[default]
+    Allow,
+    /// Skip the occurrence.
+    Skip,
+    /// Run the command once the previous run completes. At most one
+    /// occurrence is queued, so any others are skipped.
+    QueueOne,
+    /// Cancel the previous run, dropping its future, and run the command again.
+    CancelPrevious,
+}
+
 /// Contains scheduling information for a job at a given timezone.
+///
+/// The first occurrence is calculated from the scheduler's clock when the
+/// job is inserted.
 pub struct Job<Tz: TimeZoneExt> {
-    pub(crate) iterator: cron::OwnedScheduleIterator<Tz>,
-    pub(crate) next: DateTime<Tz>,
+    pub(crate) name: Option<String>,
+    pub(crate) schedule: JobSchedule<Tz>,
+    pub(crate) dst: DstPolicy,
+    pub(crate) start: Duration,
+    pub(crate) end: Option<DateTime<Tz>>,
+    pub(crate) misfire: MisfirePolicy,
+    pub(crate) overlap: OverlapPolicy,
+    pub(crate) pool: Option<String>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) timezone: Option<Tz>,
+}
+
+/// The schedule of a job.
+pub(crate) enum JobSchedule<Tz: TimeZoneExt> {
+    /// Cron schedules are evaluated with the job's DST policy.
+    Cron(Box<cron::Schedule>),
+    Custom(Box<dyn Schedule<Tz>>),
 }
 
 impl<Tz: TimeZoneExt> Job<Tz> {
+    /// Creates a job from any schedule, such as a custom [`Schedule`] implementation.
+    #[must_use]
+    pub fn new(schedule: impl Schedule<Tz> + 'static) -> Self {
+        Job::with_schedule(JobSchedule::Custom(Box::new(schedule)))
+    }
+
+    fn with_schedule(schedule: JobSchedule<Tz>) -> Self {
+        Job {
+            name: None,
+            schedule,
+            dst: DstPolicy::default(),
+            start: Duration::ZERO,
+            end: None,
+            misfire: MisfirePolicy::default(),
+            overlap: OverlapPolicy::default(),
+            pool: None,
+            timeout: None,
+            timezone: None,
+        }
+    }
+
     /// Creates a job from a cron expression string.
     ///
     /// # Errors
@@ -27,15 +121,229 @@ impl<Tz: TimeZoneExt> Job<Tz> {
     }
 
     /// Creates a job from a pre-generated cron schedule.
+    #[must_use]
+    pub fn cron_schedule(schedule: cron::Schedule) -> Self {
+        Job::with_schedule(JobSchedule::Cron(Box::new(schedule)))
+    }
+
+    /// Creates a job which fires once at the given time.
+    ///
+    /// If the time has already passed by the time the job is inserted, it
+    /// fires immediately. The job is removed from the scheduler after it fires.
+    ///
+    /// ```
+    /// use async_cron_scheduler::Job;
+    /// use chrono::{Duration, Local, NaiveTime};
+    ///
+    /// let tomorrow = Local::now().date_naive() + Duration::days(1);
+    /// let time = tomorrow.and_time(NaiveTime::from_hms_opt(17, 0, 0).unwrap());
+    /// let job = Job::at(time.and_local_timezone(Local).unwrap());
+    /// ```
+    #[must_use]
+    pub fn at(date_time: DateTime<Tz>) -> Self
+    where
+        Tz: 'static,
+        Tz::Offset: Send + Sync,
+    {
+        Job::new(At(date_time))
+    }
+
+    /// Creates a job which fires once after the duration has passed since its start.
+    ///
+    /// Jobs start when they are inserted, unless delayed with [`Job::starting_in`].
+    /// The job is removed from the scheduler after it fires.
+    ///
+    /// ```
+    /// use async_cron_scheduler::Job;
+    /// use chrono::Local;
+    /// use std::time::Duration;
+    ///
+    /// let job = Job::<Local>::after(Duration::from_secs(90));
+    /// ```
+    #[must_use]
+    pub fn after(duration: Duration) -> Self {
+        Job::new(After(duration))
+    }
+
+    /// Creates a job which fires repeatedly with a fixed interval between each occurrence.
+    ///
+    /// Unlike cron expressions, intervals are not aligned to minute or hour
+    /// boundaries. The first occurrence is when the job starts, which is on
+    /// insertion unless delayed with [`Job::starting_in`].
+    ///
+    /// ```
+    /// use async_cron_scheduler::Job;
+    /// use chrono::Local;
+    /// use std::time::Duration;
+    ///
+    /// // Every 7 minutes, starting now.
+    /// let job = Job::<Local>::every(Duration::from_secs(7 * 60));
+    ///
+    /// // Every 90 seconds, starting in 30 seconds.
+    /// let job = Job::<Local>::every(Duration::from_secs(90)).starting_in(Duration::from_secs(30));
+    /// ```
     ///
     /// # Panics
     ///
-    /// Panics at the end of time.
+    /// Panics if the interval is zero, as the job would fire endlessly.
     #[must_use]
-    #[allow(clippy::needless_pass_by_value)]
-    pub fn cron_schedule(schedule: cron::Schedule) -> Self {
-        let mut iterator = schedule.upcoming_owned(Tz::timescale());
-        let next = iterator.next().unwrap();
-        Job { iterator, next }
+    pub fn every(interval: Duration) -> Self {
+        assert!(
+            !interval.is_zero(),
+            "job interval must be greater than zero"
+        );
+        Job::new(Every::new(interval))
+    }
+
+    /// Delays the start of the job until the offset has passed since its insertion.
+    ///
+    /// Interval jobs first fire at the start, cron jobs at their first occurrence
+    /// after it, and one-shot jobs no earlier than it.
+    #[must_use]
+    pub fn starting_in(mut self, offset: Duration) -> Self {
+        self.start = offset;
+        self
+    }
+
+    /// Names the job, for display when listing scheduled jobs.
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets how occurrences which were missed will be handled.
+    ///
+    /// ```
+    /// use async_cron_scheduler::{Job, MisfirePolicy};
+    /// use chrono::Local;
+    ///
+    /// let job = Job::<Local>::cron("0 * * * * *").unwrap().misfire(MisfirePolicy::FireOnce);
+    /// ```
+    #[must_use]
+    pub fn misfire(mut self, policy: MisfirePolicy) -> Self {
+        self.misfire = policy;
+        self
+    }
+
+    /// Sets how occurrences are handled while a previous run of an async command is still running.
+    ///
+    /// ```
+    /// use async_cron_scheduler::{Job, OverlapPolicy};
+    /// use chrono::Local;
+    ///
+    /// let job = Job::<Local>::cron("0 * * * * *").unwrap().overlap(OverlapPolicy::Skip);
+    /// ```
+    #[must_use]
+    pub fn overlap(mut self, policy: OverlapPolicy) -> Self {
+        self.overlap = policy;
+        self
+    }
+
+    /// Assigns the job to a pool, whose limit on running async commands is set
+    /// with [`crate::SchedulerConfig::pool`].
+    ///
+    /// A pool which the scheduler was not configured with has no limit, so jobs
+    /// assigned to it are only limited by [`crate::SchedulerConfig::max_concurrency`].
+    #[must_use]
+    pub fn pool(mut self, name: impl Into<String>) -> Self {
+        self.pool = Some(name.into());
+        self
+    }
+
+    /// Cancels runs of the async command which are still running once the
+    /// timeout has passed on the scheduler's clock.
+    ///
+    /// ```
+    /// use async_cron_scheduler::Job;
+    /// use chrono::Local;
+    /// use std::time::Duration;
+    ///
+    /// let job = Job::<Local>::cron("0 * * * * *").unwrap().timeout(Duration::from_secs(30));
+    /// ```
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how a cron job handles local times which are skipped or repeated
+    /// by daylight saving time transitions.
+    ///
+    /// ```
+    /// use async_cron_scheduler::{DstPolicy, Job};
+    /// use chrono::Local;
+    ///
+    /// // Runs at 02:30 local time, but not on days where 02:30 is skipped.
+    /// let job = Job::<Local>::cron("0 30 2 * * *").unwrap().dst(DstPolicy::Skip);
+    /// ```
+    #[must_use]
+    pub fn dst(mut self, policy: DstPolicy) -> Self {
+        self.dst = policy;
+        self
+    }
+
+    /// Schedules the job in its own time zone, rather than the scheduler's.
+    ///
+    /// Jobs in different zones share one scheduler, firing in order of the
+    /// instants their occurrences fall on.
+    ///
+    /// ```
+    /// use async_cron_scheduler::Job;
+    /// use chrono::FixedOffset;
+    ///
+    /// // Runs at 09:00 in UTC+09:00, whatever zone the scheduler was launched with.
+    /// let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+    /// let job = Job::cron("0 0 9 * * *").unwrap().timezone(tokyo);
+    /// ```
+    #[must_use]
+    pub fn timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    /// Stops the job from firing after the given time.
+    ///
+    /// The job is removed from the scheduler once no occurrences remain.
+    #[must_use]
+    pub fn until(mut self, end: DateTime<Tz>) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// The first occurrence of this job when it is inserted at the given time.
+    pub(crate) fn first(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let start = add(&self.localize(now), self.start)?;
+
+        let first = match &self.schedule {
+            JobSchedule::Cron(schedule) => cron_next_after(schedule, &start, self.dst),
+            JobSchedule::Custom(schedule) => schedule.first(&start),
+        };
+
+        first.filter(|first| self.is_before_end(first))
+    }
+
+    /// The next occurrence of this job after the given time, if any.
+    pub(crate) fn next_after(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let after = &self.localize(after);
+
+        let next = match &self.schedule {
+            JobSchedule::Cron(schedule) => cron_next_after(schedule, after, self.dst),
+            JobSchedule::Custom(schedule) => schedule.next_after(after),
+        };
+
+        next.filter(|next| self.is_before_end(next))
+    }
+
+    /// Converts a time to the job's own time zone, if it has one.
+    fn localize(&self, date_time: &DateTime<Tz>) -> DateTime<Tz> {
+        match &self.timezone {
+            Some(timezone) => date_time.with_timezone(timezone),
+            None => date_time.clone(),
+        }
+    }
+
+    fn is_before_end(&self, date_time: &DateTime<Tz>) -> bool {
+        self.end.as_ref().is_none_or(|end| date_time <= end)
     }
 }
@@ -4,11 +4,14 @@
 //! Lightweight runtime-agnostic async task scheduler with cron expression support
 //!
 //! - **Lightweight**: Minimal dependencies because it does not rely on a runtime.
-//! - **Efficient**: Tickless design with no reference counters.
+//! - **Efficient**: Tickless design, sleeping until the next job is due.
 //! - **Runtime-Agnostic**: Bring your own runtime. No runtime dependencies.
 //! - **Async**: A single future drives the entire scheduler service.
+//! - **Async Commands**: Jobs may return futures, which are driven by the service.
 //! - **Task Scheduling**: Schedule multiple jobs with varying timeframes between them.
 //! - **Cron Expressions**: Standardized format for scheduling syntax.
+//! - **Time Zones**: Schedule in local time, UTC, a fixed offset, or a named zone with the `chrono-tz` feature.
+//! - **Testable**: A [`MockClock`] allows schedules to be driven without waiting.
 //!
 //! # Demo
 //!
@@ -21,34 +24,35 @@
 //! smol::block_on(async move {
 //!     // Creates a scheduler based on the Local timezone. Note that the `sched_service`
 //!     // contains the background job as a future for the caller to decide how to await
-//!     // it. When the scheduler is dropped, the scheduler service will exit as well.
-//!     let (mut scheduler, sched_service) = Scheduler::<Local>::launch(Timer::after);
+//!     // it. When every handle to the scheduler is dropped, the scheduler service will
+//!     // exit as well.
+//!     let (scheduler, sched_service) = Scheduler::launch(Local, Timer::after);
 //!
 //!     // Creates a job which executes every 1 seconds.
 //!     let job = Job::cron("1/1 * * * * *").unwrap();
-//!     let fizz_id = scheduler.insert(job, |id| println!("Fizz")).await;
+//!     let fizz_id = scheduler.insert(job, |id| println!("Fizz")).await.unwrap();
 //!
 //!     // Creates a job which executes every 3 seconds.
 //!     let job = Job::cron("1/3 * * * * *").unwrap();
-//!     let buzz_id = scheduler.insert(job, |id| println!("Buzz")).await;
+//!     let buzz_id = scheduler.insert(job, |id| println!("Buzz")).await.unwrap();
 //!
 //!     // Creates a job which executes every 5 seconds.
 //!     let job = Job::cron("1/5 * * * * *").unwrap();
-//!     let bazz_id = scheduler.insert(job, |id| println!("Bazz")).await;
+//!     let bazz_id = scheduler.insert(job, |id| println!("Bazz")).await.unwrap();
 //!
 //!     // A future which gradually drops jobs from the scheduler.
 //!     let dropper = async move {
 //!         Timer::after(Duration::from_secs(7)).await;
-//!         scheduler.remove(fizz_id).await;
+//!         scheduler.remove(fizz_id).await.unwrap();
 //!         println!("Fizz gone");
 //!         Timer::after(Duration::from_secs(5)).await;
-//!         scheduler.remove(buzz_id).await;
+//!         scheduler.remove(buzz_id).await.unwrap();
 //!         println!("Buzz gone");
 //!         Timer::after(Duration::from_secs(1)).await;
-//!         scheduler.remove(bazz_id).await;
+//!         scheduler.remove(bazz_id).await.unwrap();
 //!         println!("Bazz gone");
 //!
-//!         // `scheduler` is dropped here, which causes the sched_service to end.
+//!         // The only handle, `scheduler`, is dropped here, which ends the sched_service.
 //!     };
 //!
 //!     // Poll the dropper and scheduler service concurrently until both return.
@@ -56,40 +60,43 @@
 //! });
 //! ```
 
-use chrono::DateTime;
 use chrono::TimeZone;
+#[cfg(feature = "chrono-tz")]
+pub use chrono_tz;
 pub use cron;
 
+mod clock;
+mod config;
+mod error;
+mod event;
+mod guard;
 mod job;
+mod schedule;
 mod scheduler;
+mod stream;
 
+pub use self::clock::*;
+pub use self::config::*;
+pub use self::error::*;
+pub use self::event::SchedulerEvent;
+pub use self::guard::JobGuard;
 pub use self::job::*;
+pub use self::schedule::{DstPolicy, Schedule};
 pub use self::scheduler::*;
+pub use self::stream::{JobStream, StreamBuffer};
 
-/// Extensions for the chrono timezone structs.
-pub trait TimeZoneExt: TimeZone + Copy + Clone {
-    /// Constructs a default timezone struct for this timezone.
-    fn timescale() -> Self;
+/// A time zone which jobs can be scheduled in.
+///
+/// The zone is given to the scheduler when it is launched, so zones which
+/// carry a value, such as a [`chrono::FixedOffset`] or a named IANA zone from
+/// `chrono_tz` with the `chrono-tz` feature, may be used as well.
+pub trait TimeZoneExt: TimeZone + Copy + Clone + Send + Sync {}
 
-    /// Get the current time in this timezone.
-    fn now() -> DateTime<Self>;
-}
+impl TimeZoneExt for chrono::Local {}
 
-impl TimeZoneExt for chrono::Local {
-    fn timescale() -> Self {
-        Self
-    }
-    fn now() -> DateTime<Self> {
-        Self::now()
-    }
-}
+impl TimeZoneExt for chrono::Utc {}
 
-impl TimeZoneExt for chrono::Utc {
-    fn timescale() -> Self {
-        Self
-    }
+impl TimeZoneExt for chrono::FixedOffset {}
 
-    fn now() -> DateTime<Self> {
-        Self::now()
-    }
-}
+#[cfg(feature = "chrono-tz")]
+impl TimeZoneExt for chrono_tz::Tz {}
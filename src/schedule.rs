@@ -0,0 +1,244 @@
+// Copyright 2021 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::TimeZoneExt;
+use chrono::{DateTime, LocalResult, NaiveDateTime, Offset, TimeZone, Utc};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// Determines when a job fires.
+///
+/// Cron schedules implement this trait, and custom schedules such as business
+/// calendars may be supplied by implementing it and creating a job with [`crate::Job::new`].
+///
+/// ```
+/// use async_cron_scheduler::{Job, Schedule};
+/// use chrono::{DateTime, Datelike, Duration, Local, Weekday};
+///
+/// /// Fires at the top of every hour on weekdays.
+/// struct WorkingHours;
+///
+/// impl Schedule<Local> for WorkingHours {
+///     fn next_after(&self, after: &DateTime<Local>) -> Option<DateTime<Local>> {
+///         let hourly = "0 0 * * * *".parse::<cron::Schedule>().unwrap();
+///         hourly.after(after).find(|next| !matches!(next.weekday(), Weekday::Sat | Weekday::Sun))
+///     }
+/// }
+///
+/// let job = Job::new(WorkingHours);
+/// ```
+pub trait Schedule<Tz: TimeZoneExt>: Send + Sync {
+    /// The first occurrence of a job which starts at the given time.
+    ///
+    /// By default, this is the next occurrence after the start.
+    fn first(&self, start: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        self.next_after(start)
+    }
+
+    /// The next occurrence after the given time, or `None` if no occurrences remain.
+    fn next_after(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>>;
+}
+
+/// Cron schedules handle daylight saving time transitions with the default [`DstPolicy`].
+impl<Tz: TimeZoneExt> Schedule<Tz> for cron::Schedule {
+    fn next_after(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        cron_next_after(self, after, DstPolicy::default())
+    }
+}
+
+/// How cron schedules handle local times affected by daylight saving time transitions.
+///
+/// When clocks go forward, local times in the gap do not exist. When clocks go
+/// back, local times in the overlap occur twice. Jobs created from instants
+/// or durations rather than local times are unaffected.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DstPolicy {
+    /// Times in a gap fire once, shifted forward by the length of the gap.
+    /// Times in an overlap fire once, at their first occurrence.
+    ///
+    /// A job at 02:30 fires at 03:30 when clocks go forward from 02:00 to 03:00.
+    #[default]
+    Shift,
+    /// Times in a gap are skipped.
+    /// Times in an overlap fire once, at their first occurrence.
+    Skip,
+    /// Times in a gap fire once, shifted forward by the length of the gap.
+    /// Times in an overlap fire twice, once for each occurrence.
+    Twice,
+}
+
+/// The next occurrence of a cron schedule after the given time, following the DST policy.
+///
+/// The schedule is evaluated against local times, which are then mapped to
+/// instants in the time zone of `after`.
+pub(crate) fn cron_next_after<Tz: TimeZoneExt>(
+    schedule: &cron::Schedule,
+    after: &DateTime<Tz>,
+    policy: DstPolicy,
+) -> Option<DateTime<Tz>> {
+    let next = search(schedule, after, after.naive_local(), policy)?;
+
+    // Local times repeated after clocks go back precede the next local time found.
+    let offset = |date: &DateTime<Tz>| date.offset().fix().local_minus_utc();
+    if policy == DstPolicy::Twice && offset(&next) < offset(after) {
+        let start = transition(after, &next).naive_local() - chrono::Duration::seconds(1);
+        if let Some(repeated) = search(schedule, after, start, policy) {
+            return Some(repeated.min(next));
+        }
+    }
+
+    Some(next)
+}
+
+/// Searches local times after `start` for the first occurrence after `after`.
+fn search<Tz: TimeZoneExt>(
+    schedule: &cron::Schedule,
+    after: &DateTime<Tz>,
+    mut start: NaiveDateTime,
+    policy: DstPolicy,
+) -> Option<DateTime<Tz>> {
+    let timezone = after.timezone();
+
+    loop {
+        // UTC has no transitions, so it is used to iterate local times.
+        start = schedule
+            .after(&Utc.from_utc_datetime(&start))
+            .next()?
+            .naive_utc();
+
+        let candidate = match timezone.from_local_datetime(&start) {
+            LocalResult::Single(date) => Some(date),
+            // Some time zones list the later occurrence first.
+            LocalResult::Ambiguous(a, b) => {
+                let (first, second) = if a < b { (a, b) } else { (b, a) };
+                if first > *after {
+                    Some(first)
+                } else if policy == DstPolicy::Twice {
+                    Some(second)
+                } else {
+                    None
+                }
+            }
+            LocalResult::None if policy == DstPolicy::Skip => None,
+            LocalResult::None => shift(&timezone, start),
+        };
+
+        if let Some(candidate) = candidate.filter(|candidate| candidate > after) {
+            return Some(candidate);
+        }
+    }
+}
+
+/// Maps a local time in a gap using the offset from before the gap.
+fn shift<Tz: TimeZoneExt>(timezone: &Tz, local: NaiveDateTime) -> Option<DateTime<Tz>> {
+    let before = timezone
+        .from_local_datetime(&(local - chrono::Duration::days(1)))
+        .earliest()?;
+
+    Some(timezone.from_utc_datetime(&(local - before.offset().fix())))
+}
+
+/// Finds the first second after `from` which has the same offset as `to`.
+fn transition<Tz: TimeZoneExt>(from: &DateTime<Tz>, to: &DateTime<Tz>) -> DateTime<Tz> {
+    let timezone = to.timezone();
+    let offset = to.offset().fix();
+    let (mut low, mut high) = (from.timestamp(), to.timestamp());
+
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        let date = DateTime::from_timestamp(middle, 0).map(|date| date.with_timezone(&timezone));
+
+        if date.is_some_and(|date| date.offset().fix() == offset) {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+
+    DateTime::from_timestamp(high, 0)
+        .map_or_else(|| to.clone(), |date| date.with_timezone(&timezone))
+}
+
+/// Fires once at the given time, or at the start if it has already passed.
+pub(crate) struct At<Tz: TimeZoneExt>(pub DateTime<Tz>);
+
+impl<Tz: TimeZoneExt> Schedule<Tz> for At<Tz>
+where
+    Tz::Offset: Send + Sync,
+{
+    fn first(&self, start: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        Some(self.0.clone().max(start.clone()))
+    }
+
+    fn next_after(&self, _after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        None
+    }
+}
+
+/// Fires once after the duration has passed since the start.
+pub(crate) struct After(pub Duration);
+
+impl<Tz: TimeZoneExt> Schedule<Tz> for After {
+    fn first(&self, start: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        add(start, self.0)
+    }
+
+    fn next_after(&self, _after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        None
+    }
+}
+
+/// Fires at the start, and repeatedly with a fixed duration between each occurrence.
+pub(crate) struct Every {
+    interval: Duration,
+    /// The first occurrence, which every later occurrence is a multiple of the interval from.
+    anchor: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl Every {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            anchor: Mutex::new(None),
+        }
+    }
+}
+
+impl<Tz: TimeZoneExt> Schedule<Tz> for Every {
+    fn first(&self, start: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        *self.anchor.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some(start.with_timezone(&Utc));
+        Some(start.clone())
+    }
+
+    fn next_after(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let anchor = *self.anchor.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(anchor) = anchor else {
+            return add(after, self.interval);
+        };
+
+        // Rounds up to the next occurrence in the series, so that occurrences
+        // which were skipped do not shift the ones which follow them.
+        let interval = i64::try_from(self.interval.as_nanos()).ok()?;
+        let elapsed = after
+            .with_timezone(&Utc)
+            .signed_duration_since(anchor)
+            .num_nanoseconds()?;
+        let count = elapsed.div_euclid(interval).checked_add(1)?;
+        let offset = chrono::Duration::nanoseconds(count.checked_mul(interval)?);
+
+        anchor
+            .checked_add_signed(offset)
+            .map(|next| next.with_timezone(&after.timezone()))
+    }
+}
+
+/// Adds a duration to a date, returning `None` if it is out of range.
+pub(crate) fn add<Tz: TimeZoneExt>(
+    date_time: &DateTime<Tz>,
+    duration: Duration,
+) -> Option<DateTime<Tz>> {
+    chrono::Duration::from_std(duration)
+        .ok()
+        .and_then(|duration| date_time.clone().checked_add_signed(duration))
+}
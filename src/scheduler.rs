@@ -1,48 +1,155 @@
 // Copyright 2021 System76 <info@system76.com>
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::{Job, JobId, TimeZoneExt};
-use chrono::DateTime;
-use futures::future::Either;
+use crate::event::Subscribers;
+use crate::stream::{TickSender, Ticks};
+use crate::{
+    Clock, Job, JobGuard, JobId, JobInfo, JobStream, MisfirePolicy, OverlapPolicy, SchedulerConfig,
+    SchedulerError, SchedulerEvent, StreamBuffer, TimeZoneExt,
+};
+use chrono::{DateTime, Utc};
+use futures::channel::{mpsc, oneshot};
+use futures::future::{AbortHandle, Abortable, BoxFuture, Either};
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt, Stream, StreamExt};
 use slotmap::{DefaultKey, SecondaryMap, SlotMap};
-use std::{future::Future, time::Duration};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 use tachyonix::Sender;
 
 /// A scheduled command associated with a job.
 pub type Command = Box<dyn Fn(JobId) + Send + Sync>;
 
+/// A scheduled command whose future is driven by the scheduler service.
+pub type AsyncCommand = Box<dyn Fn(JobId) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// The IDs of jobs which are still scheduled, shared between the scheduler and
+/// its service so that jobs which finish on their own are forgotten by both.
+pub(crate) type Registry = Arc<Mutex<SlotMap<DefaultKey, ()>>>;
+
+/// Removes jobs without an async context, for use when dropping a [`JobStream`] or [`JobGuard`].
+///
+/// Holding a remover does not keep the scheduler service running.
+pub(crate) struct JobRemover {
+    jobs: Registry,
+    dropped: mpsc::UnboundedSender<JobId>,
+}
+
+impl JobRemover {
+    pub fn remove(&self, job: JobId) {
+        let removed = self
+            .jobs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(job.0)
+            .is_some();
+
+        if removed {
+            let _res = self.dropped.unbounded_send(job);
+        }
+    }
+}
+
+/// The command attached to a job in the scheduler service.
+enum JobCommand<Tz: TimeZoneExt> {
+    Sync(Command),
+    Async(AsyncCommand),
+    Stream(TickSender<Tz>),
+}
+
 /// Messages going into the scheduler service.
 enum SchedMessage<Tz: TimeZoneExt> {
-    Insert(JobId, Job<Tz>, Command),
+    Insert(JobId, Box<Job<Tz>>, JobCommand<Tz>),
     Remove(JobId),
+    Reschedule(JobId, Box<Job<Tz>>),
+    ReplaceCommand(JobId, JobCommand<Tz>),
+    Pause(JobId),
+    Resume(JobId, ResumeMode),
+    CancelRunning(JobId),
+    NextRun(JobId, oneshot::Sender<Option<DateTime<Tz>>>),
+    List(oneshot::Sender<Vec<JobInfo<Tz>>>),
+    Shutdown(ShutdownMode, oneshot::Sender<()>),
+    Subscribe(mpsc::UnboundedSender<SchedulerEvent<Tz>>),
+}
+
+/// How far the wall clock may drift from the monotonic clock before it is considered to have jumped.
+const JUMP_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// How a paused job handles the occurrences it missed while paused.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ResumeMode {
+    /// Fire occurrences that were missed while paused, as permitted by the job's [`MisfirePolicy`].
+    FireMissed,
+    /// Skip missed occurrences, resuming from the next occurrence after now.
+    SkipMissed,
+}
+
+/// How the scheduler service stops when shut down with [`Scheduler::shutdown`].
+///
+/// In every mode, jobs stop firing and further requests to the scheduler are
+/// refused as soon as the service receives the request.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShutdownMode {
+    /// Exit immediately, dropping any async commands which are still running.
+    Now,
+    /// Exit once every async command which is still running has completed.
+    Drain,
+    /// Wait for running async commands to complete, but exit once the timeout
+    /// has passed on the scheduler's clock, dropping any which remain.
+    Deadline(Duration),
 }
 
 /// The interface for interacting with the scheduler.
 ///
 /// When launching a scheduler, the scheduler and its service are created
 /// simultaneously with a channel connecting them. Job insert and remove
-/// messages are sent to the service for automatic management. When the
-/// scheduler is dropped, so too will the service its attached to exit.
+/// messages are sent to the service for automatic management.
 ///
-/// ```
+/// The scheduler is a cheaply cloneable handle, so it may be shared between
+/// tasks which insert and remove jobs concurrently. Job IDs are allocated
+/// from a registry shared by every handle. When the last handle is dropped,
+/// so too will the service its attached to exit.
+///
+/// ```no_run
+/// use async_cron_scheduler::{Job, Scheduler};
 /// use smol::Timer;
 /// use chrono::offset::Local;
 ///
-/// let (mut scheduler, service) = Scheduler::<Local>::launch(Timer::after);
+/// # smol::block_on(async {
+/// let (scheduler, service) = Scheduler::launch(Local, Timer::after);
 ///
 /// // Creates a job which executes every 3 seconds.
 /// let job = Job::cron("1/3 * * * * *").unwrap();
-/// let fizz_id = scheduler.insert(job, |id| println!("Fizz")).await;
+/// let fizz_id = scheduler.insert(job, |id| println!("Fizz")).await.unwrap();
 ///
 /// // Creates a job which executes every 5 seconds.
 /// let job = Job::cron("1/5 * * * * *").unwrap();
-/// let buzz_id = scheduler.insert(job, |id| println!("Buzz")).await;
+/// let buzz_id = scheduler.insert(job, |id| println!("Buzz")).await.unwrap();
 ///
 /// service.await;
+/// # });
 /// ```
 pub struct Scheduler<Tz: TimeZoneExt> {
-    jobs: SlotMap<DefaultKey, ()>,
+    jobs: Registry,
     sender: Sender<SchedMessage<Tz>>,
+    /// Removes jobs from outside of an async context, such as when a [`JobGuard`] is dropped.
+    dropped: mpsc::UnboundedSender<JobId>,
+}
+
+/// A handle to a scheduler service, which may be cloned to share the service.
+pub type SchedulerHandle<Tz> = Scheduler<Tz>;
+
+impl<Tz: TimeZoneExt> Clone for Scheduler<Tz> {
+    fn clone(&self) -> Self {
+        Self {
+            jobs: self.jobs.clone(),
+            sender: self.sender.clone(),
+            dropped: self.dropped.clone(),
+        }
+    }
 }
 
 impl<Tz: TimeZoneExt + 'static> Scheduler<Tz>
@@ -51,176 +158,1377 @@ where
 {
     /// Insert a job into the scheduler with the command to call when scheduled.
     ///
-    /// ```
+    /// # Errors
+    ///
+    /// Errors if the scheduler service has stopped, in which case the job will never run.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler};
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
     /// // Creates a job which executes every 3 seconds.
     /// let job = Job::cron("1/3 * * * * *").unwrap();
-    /// let fizz_id = scheduler.insert(job, |id| println!("Fizz")).await;
+    /// let fizz_id = scheduler.insert(job, |id| println!("Fizz")).await?;
+    /// # Ok::<(), async_cron_scheduler::SchedulerError>(())
+    /// # });
     /// ```
     pub async fn insert(
-        &mut self,
+        &self,
         job: Job<Tz>,
         command: impl Fn(JobId) + Send + Sync + 'static,
-    ) -> JobId {
-        let id = JobId(self.jobs.insert(()));
-        let _result = self
-            .sender
-            .send(SchedMessage::Insert(id, job, Box::new(command)))
-            .await;
-        id
+    ) -> Result<JobId, SchedulerError> {
+        self.insert_command(job, JobCommand::Sync(Box::new(command)))
+            .await
+    }
+
+    /// Insert a job into the scheduler with an async command to call when scheduled.
+    ///
+    /// The future returned by the command is driven by the scheduler service
+    /// itself, so no runtime is needed to spawn it. Commands of the same job
+    /// may run concurrently if a previous future has yet to complete, unless
+    /// prevented with [`Job::overlap`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the scheduler service has stopped, in which case the job will never run.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler};
+    /// # use std::time::Duration;
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// // Creates a job which executes every 3 seconds.
+    /// let job = Job::cron("1/3 * * * * *").unwrap();
+    /// let fizz_id = scheduler
+    ///     .insert_async(job, |id| async move {
+    ///         smol::Timer::after(Duration::from_secs(1)).await;
+    ///         println!("Fizz");
+    ///     })
+    ///     .await?;
+    /// # Ok::<(), async_cron_scheduler::SchedulerError>(())
+    /// # });
+    /// ```
+    pub async fn insert_async<F>(
+        &self,
+        job: Job<Tz>,
+        command: impl Fn(JobId) -> F + Send + Sync + 'static,
+    ) -> Result<JobId, SchedulerError>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let command: AsyncCommand = Box::new(move |id| command(id).boxed());
+        self.insert_command(job, JobCommand::Async(command)).await
+    }
+
+    /// Sends a message to the service, failing if it has stopped.
+    async fn send(&self, message: SchedMessage<Tz>) -> Result<(), SchedulerError> {
+        self.sender
+            .send(message)
+            .await
+            .map_err(|_| SchedulerError::ServiceStopped)
+    }
+
+    /// Sends a message about a job to the service, failing if the job is no
+    /// longer scheduled.
+    async fn send_to(&self, job: JobId, message: SchedMessage<Tz>) -> Result<(), SchedulerError> {
+        if self.contains(job) {
+            self.send(message).await
+        } else if self.is_running() {
+            Err(SchedulerError::UnknownJob)
+        } else {
+            Err(SchedulerError::ServiceStopped)
+        }
+    }
+
+    async fn insert_command(
+        &self,
+        job: Job<Tz>,
+        command: JobCommand<Tz>,
+    ) -> Result<JobId, SchedulerError> {
+        let id = JobId(self.jobs().insert(()));
+        let message = SchedMessage::Insert(id, Box::new(job), command);
+
+        if self.sender.send(message).await.is_err() {
+            self.jobs().remove(id.0);
+            return Err(SchedulerError::ServiceStopped);
+        }
+
+        Ok(id)
+    }
+
+    /// Insert a job into the scheduler, receiving its occurrences from a stream
+    /// rather than calling a command.
+    ///
+    /// Occurrences which the consumer has yet to receive are buffered without
+    /// limit. The job is removed from the scheduler when the stream is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the scheduler service has stopped, in which case the job will never run.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler};
+    /// # use futures::StreamExt;
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// let job = Job::cron("1/3 * * * * *").unwrap();
+    /// let mut ticks = scheduler.stream(job).await?;
+    ///
+    /// while let Some((id, scheduled)) = ticks.next().await {
+    ///     println!("Fizz at {scheduled}");
+    /// }
+    /// # Ok::<(), async_cron_scheduler::SchedulerError>(())
+    /// # });
+    /// ```
+    pub async fn stream(&self, job: Job<Tz>) -> Result<JobStream<Tz>, SchedulerError> {
+        self.stream_with(job, StreamBuffer::default()).await
+    }
+
+    /// Insert a job into the scheduler, receiving its occurrences from a stream
+    /// which buffers occurrences as configured.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the scheduler service has stopped, in which case the job will never run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is bounded with a capacity of zero.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler, StreamBuffer};
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// // A slow consumer only receives the latest occurrence.
+    /// let job = Job::cron("* * * * * *").unwrap();
+    /// let ticks = scheduler.stream_with(job, StreamBuffer::DropOldest(1)).await?;
+    /// # Ok::<(), async_cron_scheduler::SchedulerError>(())
+    /// # });
+    /// ```
+    pub async fn stream_with(
+        &self,
+        job: Job<Tz>,
+        buffer: StreamBuffer,
+    ) -> Result<JobStream<Tz>, SchedulerError> {
+        let ticks = Arc::new(Mutex::new(Ticks::new(buffer)));
+        let command = JobCommand::Stream(TickSender(ticks.clone()));
+        let id = self.insert_command(job, command).await?;
+
+        Ok(JobStream::new(id, ticks, self.remover()))
+    }
+
+    /// Insert a job into the scheduler, returning a guard which removes the job when dropped.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the scheduler service has stopped, in which case the job will never run.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler};
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// let job = Job::cron("1/3 * * * * *").unwrap();
+    /// let guard = scheduler.insert_guarded(job, |id| println!("Fizz")).await?;
+    ///
+    /// // Fizz is removed from the scheduler here.
+    /// drop(guard);
+    /// # Ok::<(), async_cron_scheduler::SchedulerError>(())
+    /// # });
+    /// ```
+    pub async fn insert_guarded(
+        &self,
+        job: Job<Tz>,
+        command: impl Fn(JobId) + Send + Sync + 'static,
+    ) -> Result<JobGuard, SchedulerError> {
+        let id = self.insert(job, command).await?;
+        Ok(JobGuard::new(id, self.remover()))
     }
 
     /// Remove a scheduled job from the scheduler.
     ///
+    /// # Errors
+    ///
+    /// Errors if the scheduler service has stopped. The job is forgotten regardless.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler};
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// # let fizz_id = scheduler.insert(Job::cron("* * * * * *").unwrap(), |_| ()).await?;
+    /// scheduler.remove(fizz_id).await?;
+    /// # Ok::<(), async_cron_scheduler::SchedulerError>(())
+    /// # });
     /// ```
-    /// scheduler.remove(fizz_id).await;
+    pub async fn remove(&self, job: JobId) -> Result<(), SchedulerError> {
+        let removed = self.jobs().remove(job.0).is_some();
+
+        if removed {
+            self.send(SchedMessage::Remove(job)).await
+        } else if self.is_running() {
+            Ok(())
+        } else {
+            Err(SchedulerError::ServiceStopped)
+        }
+    }
+
+    /// Whether the job is still scheduled.
+    ///
+    /// Jobs are no longer scheduled once removed, once they have no occurrences
+    /// left, such as one-shot jobs which have fired, or once the service has exited.
+    #[must_use]
+    pub fn contains(&self, job: JobId) -> bool {
+        self.jobs().contains_key(job.0)
+    }
+
+    /// The number of jobs which are still scheduled, including paused jobs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.jobs().len()
+    }
+
+    /// Whether no jobs are scheduled.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.jobs().is_empty()
+    }
+
+    fn jobs(&self) -> MutexGuard<'_, SlotMap<DefaultKey, ()>> {
+        self.jobs.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn remover(&self) -> JobRemover {
+        JobRemover {
+            jobs: self.jobs.clone(),
+            dropped: self.dropped.clone(),
+        }
+    }
+
+    /// Stops the scheduler service, resolving once the service has exited.
+    ///
+    /// The service exits when the mode allows it, even if handles to the
+    /// scheduler remain. If the service has already exited, this resolves
+    /// immediately.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Scheduler, ShutdownMode};
+    /// # use std::time::Duration;
+    /// # smol::block_on(async {
+    /// # let (scheduler, service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// # smol::spawn(service).detach();
+    /// // Give running commands up to 30 seconds to complete.
+    /// scheduler.shutdown(ShutdownMode::Deadline(Duration::from_secs(30))).await;
+    /// # });
     /// ```
-    pub async fn remove(&mut self, job: JobId) {
-        if self.jobs.remove(job.0).is_some() {
-            let _res = self.sender.send(SchedMessage::Remove(job)).await;
+    pub async fn shutdown(&self, mode: ShutdownMode) {
+        let (reply, response) = oneshot::channel();
+        if self
+            .sender
+            .send(SchedMessage::Shutdown(mode, reply))
+            .await
+            .is_ok()
+        {
+            let _res = response.await;
         }
     }
 
+    /// Subscribes to events describing the activity of the scheduler service.
+    ///
+    /// Events which occur after the subscription is received by the service
+    /// are delivered in order. The stream ends when the service exits.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Scheduler, SchedulerEvent};
+    /// # use futures::StreamExt;
+    /// # smol::block_on(async {
+    /// # let (scheduler, service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// let mut events = scheduler.subscribe().await;
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     if let SchedulerEvent::Fired { id, scheduled, actual } = event {
+    ///         println!("{id:?} fired {} late", actual - scheduled);
+    ///     }
+    /// }
+    /// # });
+    /// ```
+    pub async fn subscribe(&self) -> impl Stream<Item = SchedulerEvent<Tz>> + Send + Unpin {
+        let (sender, receiver) = mpsc::unbounded();
+        let _res = self.sender.send(SchedMessage::Subscribe(sender)).await;
+        receiver
+    }
+
+    /// Whether the scheduler service is still running.
+    ///
+    /// Once the service future has been dropped or has panicked, jobs will
+    /// no longer fire, and inserting or removing jobs returns an error.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        !self.sender.is_closed()
+    }
+
+    /// Replace the schedule of a job in place, keeping its ID and command.
+    ///
+    /// The next occurrence is calculated from the new job as if it were just inserted.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the job is no longer scheduled, or if the scheduler service has stopped.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler};
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// # let fizz_id = scheduler.insert(Job::cron("* * * * * *").unwrap(), |_| ()).await.unwrap();
+    /// // Fizz every 2 seconds instead.
+    /// let job = Job::cron("1/2 * * * * *").unwrap();
+    /// scheduler.reschedule(fizz_id, job).await?;
+    /// # Ok::<(), async_cron_scheduler::SchedulerError>(())
+    /// # });
+    /// ```
+    pub async fn reschedule(&self, job: JobId, schedule: Job<Tz>) -> Result<(), SchedulerError> {
+        self.send_to(job, SchedMessage::Reschedule(job, Box::new(schedule)))
+            .await
+    }
+
+    /// Replace the command of a job in place, keeping its ID and schedule.
+    ///
+    /// Jobs created by [`SchedulerHandle::stream`] keep sending to their stream,
+    /// as their command cannot be replaced.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the job is no longer scheduled, or if the scheduler service has stopped.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler};
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// # let fizz_id = scheduler.insert(Job::cron("* * * * * *").unwrap(), |_| ()).await.unwrap();
+    /// scheduler.replace_command(fizz_id, |id| println!("FizzBuzz")).await?;
+    /// # Ok::<(), async_cron_scheduler::SchedulerError>(())
+    /// # });
+    /// ```
+    pub async fn replace_command(
+        &self,
+        job: JobId,
+        command: impl Fn(JobId) + Send + Sync + 'static,
+    ) -> Result<(), SchedulerError> {
+        self.send_to(
+            job,
+            SchedMessage::ReplaceCommand(job, JobCommand::Sync(Box::new(command))),
+        )
+        .await
+    }
+
+    /// Replace the command of a job in place with an async command.
+    ///
+    /// As with [`SchedulerHandle::replace_command`], streamed jobs keep their stream.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the job is no longer scheduled, or if the scheduler service has stopped.
+    pub async fn replace_command_async<F>(
+        &self,
+        job: JobId,
+        command: impl Fn(JobId) -> F + Send + Sync + 'static,
+    ) -> Result<(), SchedulerError>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let command: AsyncCommand = Box::new(move |id| command(id).boxed());
+        self.send_to(
+            job,
+            SchedMessage::ReplaceCommand(job, JobCommand::Async(command)),
+        )
+        .await
+    }
+
+    /// Pause a scheduled job, keeping it in the scheduler without firing it.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the job is no longer scheduled, or if the scheduler service has stopped.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler, ResumeMode};
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// # let fizz_id = scheduler.insert(Job::cron("* * * * * *").unwrap(), |_| ()).await.unwrap();
+    /// scheduler.pause(fizz_id).await?;
+    /// scheduler.resume(fizz_id, ResumeMode::SkipMissed).await?;
+    /// # Ok::<(), async_cron_scheduler::SchedulerError>(())
+    /// # });
+    /// ```
+    pub async fn pause(&self, job: JobId) -> Result<(), SchedulerError> {
+        self.send_to(job, SchedMessage::Pause(job)).await
+    }
+
+    /// Resume a paused job, with the mode deciding whether missed occurrences will fire.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the job is no longer scheduled, or if the scheduler service has stopped.
+    pub async fn resume(&self, job: JobId, mode: ResumeMode) -> Result<(), SchedulerError> {
+        self.send_to(job, SchedMessage::Resume(job, mode)).await
+    }
+
+    /// Cancel the runs of a job's async command which are still running.
+    ///
+    /// Occurrences which have yet to start, such as those queued by its
+    /// [`OverlapPolicy`] or deferred by a concurrency limit, are unaffected.
+    /// Runs may be cancelled even after the job has been removed, or has no
+    /// occurrences left.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the scheduler service has stopped.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler};
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// # let job = Job::cron("* * * * * *").unwrap();
+    /// let id = scheduler.insert_async(job, |_| futures::future::pending()).await?;
+    /// scheduler.cancel_running(id).await?;
+    /// # Ok::<(), async_cron_scheduler::SchedulerError>(())
+    /// # });
+    /// ```
+    pub async fn cancel_running(&self, job: JobId) -> Result<(), SchedulerError> {
+        self.send(SchedMessage::CancelRunning(job)).await
+    }
+
+    /// The next time that a scheduled job will fire, if it is still scheduled.
+    ///
+    /// Paused jobs are not scheduled to fire, so `None` is returned for them.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler};
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// # let fizz_id = scheduler.insert(Job::cron("* * * * * *").unwrap(), |_| ()).await.unwrap();
+    /// if let Some(next) = scheduler.next_run(fizz_id).await {
+    ///     println!("Fizz at {next}");
+    /// }
+    /// # });
+    /// ```
+    pub async fn next_run(&self, job: JobId) -> Option<DateTime<Tz>> {
+        let (reply, response) = oneshot::channel();
+        let _res = self.sender.send(SchedMessage::NextRun(job, reply)).await;
+        response.await.ok().flatten()
+    }
+
+    /// Lists every scheduled job, ordered by the time that they will next fire.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Job, Scheduler};
+    /// # smol::block_on(async {
+    /// # let (scheduler, _service) = Scheduler::launch(chrono::Local, smol::Timer::after);
+    /// for job in scheduler.list().await {
+    ///     println!("{}: {}", job.name.as_deref().unwrap_or("unnamed"), job.next);
+    /// }
+    /// # });
+    /// ```
+    pub async fn list(&self) -> Vec<JobInfo<Tz>> {
+        let (reply, response) = oneshot::channel();
+        let _res = self.sender.send(SchedMessage::List(reply)).await;
+        response.await.unwrap_or_default()
+    }
+
     /// Initializes the scheduler and its connected service.
     ///
     /// The API is designed to not rely on any async runtimes. This is achieved by
     /// returning a future to allow the caller to decide how it should be executed,
-    /// and taking a function for handling sleeps. You can choose to spawn the
+    /// and taking a [`Clock`] for handling sleeps. Any timer function such as
+    /// `smol::Timer::after` can be used as a clock based on the system time, whereas
+    /// a [`crate::MockClock`] can be used to drive jobs in tests. You can choose to spawn the
     /// returned future, or avoid spawning altgether and await it directly from the
     /// same thread.
     ///
+    /// Futures returned by async commands are polled by the service as well,
+    /// so they will run on whichever executor the service is awaited on. Any
+    /// commands still in flight are dropped when the service exits. As those
+    /// futures need not be `Sync`, the service future is `Send` but not `Sync`.
+    ///
+    /// Jobs are scheduled in the given time zone, which determines how cron
+    /// expressions and daylight saving time transitions are interpreted.
+    ///
     /// ## Smol runtime
     ///
-    /// ```
-    /// let (mut scheduler, sched_service) = Scheduler::<Local>::launch(smol::Timer::after);
+    /// ```no_run
+    /// # use async_cron_scheduler::Scheduler;
+    /// # use chrono::Local;
+    /// let (scheduler, sched_service) = Scheduler::launch(Local, smol::Timer::after);
     /// smol::spawn(sched_service).detach();
     /// ```
     ///
     /// ## Tokio runtime
     ///
-    /// ```
-    /// let (mut scheduler, sched_service) = Scheduler::<Local>::launch(tokio::time::sleep);
+    /// ```ignore
+    /// let (scheduler, sched_service) = Scheduler::launch(Local, tokio::time::sleep);
     /// tokio::spawn(sched_service);
     /// ```
-    pub fn launch<F, T, X>(timer: T) -> (Self, impl Future<Output = ()> + Send + Sync + 'static)
-    where
-        F: Future<Output = X> + Send + Sync,
-        T: Fn(Duration) -> F + Send + Sync + 'static,
-    {
+    ///
+    /// ## Fixed offset
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::Scheduler;
+    /// # use chrono::FixedOffset;
+    /// let timezone = FixedOffset::east_opt(9 * 3600).unwrap();
+    /// let (scheduler, sched_service) = Scheduler::launch(timezone, smol::Timer::after);
+    /// ```
+    pub fn launch<C: Clock>(
+        timezone: Tz,
+        clock: C,
+    ) -> (Self, impl Future<Output = ()> + Send + 'static) {
+        Self::launch_with(timezone, clock, SchedulerConfig::default())
+    }
+
+    /// Initializes the scheduler and its connected service with additional options.
+    ///
+    /// ```no_run
+    /// # use async_cron_scheduler::{Scheduler, SchedulerConfig};
+    /// # use chrono::Local;
+    /// # use std::time::Duration;
+    /// // Checks for changes to the system clock at least once a minute.
+    /// let config = SchedulerConfig::default().resync(Duration::from_secs(60));
+    /// let (scheduler, sched_service) = Scheduler::launch_with(Local, smol::Timer::after, config);
+    /// smol::spawn(sched_service).detach();
+    /// ```
+    pub fn launch_with<C: Clock>(
+        timezone: Tz,
+        clock: C,
+        config: SchedulerConfig,
+    ) -> (Self, impl Future<Output = ()> + Send + 'static) {
         let (sender, mut receiver) = tachyonix::channel(16);
+        let (dropped, mut removals) = mpsc::unbounded();
+        let jobs = Registry::default();
 
-        let task = async move {
-            let mut state = SchedulerModel {
-                tasks: SecondaryMap::new(),
-                next: None,
-            };
+        let task = {
+            let jobs = jobs.clone();
+            async move {
+                let mut state = SchedulerModel {
+                    jobs,
+                    last_wake: (clock.now(), clock.instant()),
+                    timezone,
+                    clock,
+                    config,
+                    tasks: SecondaryMap::new(),
+                    queue: BinaryHeap::new(),
+                    next: None,
+                    running: FuturesUnordered::new(),
+                    runs: 0,
+                    in_flight: HashMap::new(),
+                    active: (0, HashMap::new()),
+                    deferred: VecDeque::new(),
+                    stopping: None,
+                    subscribers: Subscribers::default(),
+                };
+
+                loop {
+                    if state.has_stopped() {
+                        break;
+                    }
+
+                    let sleep = state.sleep();
+                    let accepting = state.stopping.is_none();
+
+                    let event = {
+                        // Requests are refused while shutting down.
+                        let message = async {
+                            if accepting {
+                                ServiceEvent::Message(receiver.recv().await.ok())
+                            } else {
+                                futures::future::pending().await
+                            }
+                        };
+
+                        // Jobs whose stream or guard was dropped.
+                        let removal = async {
+                            match removals.next().await {
+                                Some(id) => ServiceEvent::Dropped(id),
+                                None => futures::future::pending().await,
+                            }
+                        };
 
-            loop {
-                match state.next.take() {
-                    Some((key, duration)) => {
-                        let message = receiver.recv();
-                        let wait = timer(duration);
+                        let wait = async {
+                            match sleep {
+                                Some(sleep) => {
+                                    sleep.await;
+                                    ServiceEvent::Timer
+                                }
+                                None => futures::future::pending().await,
+                            }
+                        };
+
+                        let running = async {
+                            match state.running.next().await {
+                                Some(run) => ServiceEvent::Finished(run),
+                                None => futures::future::pending().await,
+                            }
+                        };
 
                         futures::pin_mut!(message);
+                        futures::pin_mut!(removal);
                         futures::pin_mut!(wait);
+                        futures::pin_mut!(running);
 
-                        match futures::future::select(message, wait).await {
-                            Either::Left((message, _)) => match message {
-                                Ok(message) => state.update(message),
-                                Err(_) => break,
-                            },
+                        let requests = futures::future::select(message, removal);
+                        let wakes = futures::future::select(wait, running);
 
-                            Either::Right((_, _)) => {
-                                state.call(key);
-                                state.next();
-                            }
+                        match futures::future::select(requests, wakes).await {
+                            Either::Left((event, _)) => event.factor_first().0,
+                            Either::Right((event, _)) => event.factor_first().0,
                         }
+                    };
+
+                    if state.clock_jumped() {
+                        state.next();
                     }
 
-                    None => match receiver.recv().await {
-                        Ok(message) => state.update(message),
-                        Err(_) => break,
-                    },
-                };
+                    match event {
+                        ServiceEvent::Message(Some(message)) => state.update(message),
+                        ServiceEvent::Message(None) => break,
+                        ServiceEvent::Dropped(id) => state.update(SchedMessage::Remove(id)),
+                        ServiceEvent::Timer => state.next(),
+                        ServiceEvent::Finished(run) => state.finished(run),
+                    }
+
+                    if state.stopping.is_some() {
+                        receiver.close();
+                    }
+                }
+
+                // Jobs will never run again, so handles no longer report them as scheduled.
+                state
+                    .jobs
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .clear();
+
+                // Commands still running are dropped before the service reports that it has exited.
+                let replies = state.stopping.take().map(|stop| stop.replies);
+                drop(state);
+
+                for reply in replies.into_iter().flatten() {
+                    let _res = reply.send(());
+                }
             }
         };
 
         (
             Self {
                 sender,
-                jobs: SlotMap::new(),
+                jobs,
+                dropped,
             },
             task,
         )
     }
 }
-struct SchedulerModel<Tz: TimeZoneExt> {
-    tasks: SecondaryMap<DefaultKey, (Job<Tz>, Command)>,
-    next: Option<(DefaultKey, Duration)>,
+
+/// A shutdown in progress.
+struct Stopping {
+    /// When to exit, even if commands are still running.
+    deadline: Option<DateTime<Utc>>,
+    /// Notified once the service has exited.
+    replies: Vec<oneshot::Sender<()>>,
+}
+
+/// What woke the scheduler service.
+enum ServiceEvent<Tz: TimeZoneExt> {
+    Message(Option<SchedMessage<Tz>>),
+    Dropped(JobId),
+    Timer,
+    Finished(Run),
+}
+
+/// A run of an async command which has finished.
+struct Run {
+    id: JobId,
+    /// The pool that the run occupied a slot in.
+    pool: Option<String>,
+    /// Identifies the run among others of the same job.
+    number: u64,
+    outcome: Outcome,
+}
+
+/// How a run of an async command finished.
+enum Outcome {
+    Completed,
+    Cancelled,
+    TimedOut,
+}
+
+/// A job being managed by the scheduler service.
+struct Task<Tz: TimeZoneExt> {
+    job: Job<Tz>,
+    command: JobCommand<Tz>,
+    next: DateTime<Tz>,
+    paused: bool,
+    /// An occurrence waiting for the previous run to complete, with [`OverlapPolicy::QueueOne`].
+    queued: Option<DateTime<Tz>>,
+    /// How many occurrences are waiting for a concurrency limit.
+    deferred: usize,
+}
+
+impl<Tz: TimeZoneExt> Task<Tz> {
+    /// Whether a queue entry for the given time is current for this task.
+    fn is_queued_at(&self, date: &DateTime<Utc>) -> bool {
+        !self.paused && self.next == *date
+    }
+}
+
+struct SchedulerModel<Tz: TimeZoneExt, C: Clock> {
+    jobs: Registry,
+    timezone: Tz,
+    clock: C,
+    config: SchedulerConfig,
+    /// The wall and monotonic time when the service last woke.
+    last_wake: (DateTime<Utc>, Instant),
+    tasks: SecondaryMap<DefaultKey, Task<Tz>>,
+    /// Upcoming fire times, earliest first. Times are kept in UTC, because
+    /// tasks may be in different time zones. Entries of removed or rescheduled
+    /// tasks are left in place and discarded once they reach the top.
+    queue: BinaryHeap<Reverse<(DateTime<Utc>, DefaultKey)>>,
+    next: Option<(DefaultKey, DateTime<Utc>)>,
+    /// Futures of async commands, which resolve once finished.
+    running: FuturesUnordered<BoxFuture<'static, Run>>,
+    /// The number given to the next run of an async command.
+    runs: u64,
+    /// Runs of async commands which are still running, by job. These are kept
+    /// apart from the tasks, so that runs outlive the removal of their job.
+    in_flight: HashMap<DefaultKey, Vec<(u64, AbortHandle)>>,
+    /// How many async commands are running, in total and by pool.
+    active: (usize, HashMap<String, usize>),
+    /// Occurrences of async commands waiting for a concurrency limit, in the order they fired.
+    deferred: VecDeque<(DefaultKey, DateTime<Tz>)>,
+    stopping: Option<Stopping>,
+    subscribers: Subscribers<Tz>,
 }
 
-impl<Tz: TimeZoneExt> SchedulerModel<Tz> {
-    pub fn call(&mut self, key: DefaultKey) {
-        if let Some((job, func)) = self.tasks.get_mut(key) {
-            func(JobId(key));
+impl<Tz: TimeZoneExt, C: Clock> SchedulerModel<Tz, C> {
+    pub fn now(&self) -> DateTime<Tz> {
+        self.clock.now().with_timezone(&self.timezone)
+    }
+
+    /// Sleeps until the next job, or the next resync if that comes first.
+    ///
+    /// While shutting down, sleeps until the shutdown deadline instead.
+    fn sleep(&self) -> Option<C::Sleep> {
+        if let Some(stopping) = &self.stopping {
+            return stopping.deadline.map(|d| self.clock.sleep_until(d));
+        }
+
+        let (_, mut deadline) = self.next?;
 
-            if let Some(next) = job.iterator.next() {
-                job.next = next;
+        if let Some(resync) = self.config.resync {
+            let resync = chrono::Duration::from_std(resync).ok();
+            if let Some(resync) = resync.and_then(|d| self.clock.now().checked_add_signed(d)) {
+                deadline = deadline.min(resync);
+            }
+        }
+
+        Some(self.clock.sleep_until(deadline))
+    }
+
+    /// Checks if the wall clock has drifted from the monotonic clock since the last wake.
+    fn clock_jumped(&mut self) -> bool {
+        let (wall, instant) = (self.clock.now(), self.clock.instant());
+        let (last_wall, last_instant) = std::mem::replace(&mut self.last_wake, (wall, instant));
+
+        let elapsed = chrono::Duration::from_std(instant.duration_since(last_instant));
+        let drift = elapsed.map(|elapsed| wall.signed_duration_since(last_wall) - elapsed);
+
+        match drift {
+            Ok(drift)
+                if drift
+                    .abs()
+                    .to_std()
+                    .is_ok_and(|drift| drift <= JUMP_THRESHOLD) =>
+            {
+                false
+            }
+            _drift => {
+                #[cfg(feature = "logging")]
+                tracing::warn!("wall clock jumped by {:?}", _drift);
+
+                true
+            }
+        }
+    }
+
+    pub fn call(&mut self, key: DefaultKey, now: &DateTime<Tz>) {
+        if let Some(task) = self.tasks.get_mut(key) {
+            let late = now.clone().signed_duration_since(&task.next);
+            let missed = late > chrono::Duration::from_std(MisfirePolicy::THRESHOLD).unwrap();
+
+            let (fire, skip_rest) = match task.job.misfire {
+                _ if !missed => (true, false),
+                MisfirePolicy::FireAll => (true, false),
+                MisfirePolicy::FireOnce => (true, true),
+                MisfirePolicy::Skip => (false, true),
+                MisfirePolicy::Grace(grace) => (
+                    chrono::Duration::from_std(grace).map_or(true, |grace| late <= grace),
+                    false,
+                ),
+            };
+
+            let scheduled = task.next.clone();
+
+            if fire {
+                self.fire(key, scheduled, now);
+            } else {
+                #[cfg(feature = "logging")]
+                tracing::warn!("skipped job missed at {:?}", scheduled);
+
+                self.subscribers.emit(SchedulerEvent::Skipped {
+                    id: JobId(key),
+                    scheduled,
+                });
+            }
+
+            let Some(task) = self.tasks.get_mut(key) else {
+                return;
+            };
+
+            let next = if skip_rest {
+                let mut next = task.job.next_after(&task.next);
+
+                while let Some(scheduled) = next.clone().filter(|date| date <= now) {
+                    #[cfg(feature = "logging")]
+                    tracing::warn!("skipped job missed at {:?}", scheduled);
+
+                    next = task.job.next_after(&scheduled);
+                    self.subscribers.emit(SchedulerEvent::Skipped {
+                        id: JobId(key),
+                        scheduled,
+                    });
+                }
+
+                next
+            } else {
+                task.job.next_after(&task.next)
+            };
+
+            if let Some(next) = next {
+                self.queue.push(Reverse((next.with_timezone(&Utc), key)));
+                task.next = next;
 
                 return;
             }
 
-            self.tasks.remove(key);
+            self.finish(key);
         }
     }
 
-    pub fn next(&mut self) {
-        loop {
-            let mut next: Option<(DefaultKey, DateTime<Tz>)> = None;
+    /// Calls the command of a task for an occurrence, as permitted by its
+    /// overlap policy and the concurrency limits.
+    fn fire(&mut self, key: DefaultKey, scheduled: DateTime<Tz>, now: &DateTime<Tz>) {
+        let Some(task) = self.tasks.get(key) else {
+            return;
+        };
+
+        let limited = matches!(task.command, JobCommand::Async(_))
+            && !self.has_slot(task.job.pool.as_deref());
 
-            for (id, (job, _)) in &self.tasks {
-                if let Some((_, date_time)) = next.as_ref() {
-                    if date_time.timestamp() > job.next.timestamp() {
-                        next = Some((id, job.next.clone()));
+        let Some(task) = self.tasks.get_mut(key) else {
+            return;
+        };
+
+        let id = JobId(key);
+
+        if self.in_flight.contains_key(&key) || task.deferred > 0 {
+            match task.job.overlap {
+                OverlapPolicy::Allow => (),
+
+                OverlapPolicy::Skip => {
+                    #[cfg(feature = "logging")]
+                    tracing::warn!("skipped job at {:?}, as it is still running", scheduled);
+
+                    self.subscribers
+                        .emit(SchedulerEvent::Skipped { id, scheduled });
+                    return;
+                }
+
+                OverlapPolicy::QueueOne if task.queued.is_none() => {
+                    #[cfg(feature = "logging")]
+                    tracing::info!("queued job at {:?}, as it is still running", scheduled);
+
+                    task.queued = Some(scheduled);
+                    return;
+                }
+
+                OverlapPolicy::QueueOne => {
+                    #[cfg(feature = "logging")]
+                    tracing::warn!("skipped job at {:?}, as a run is already queued", scheduled);
+
+                    self.subscribers
+                        .emit(SchedulerEvent::Skipped { id, scheduled });
+                    return;
+                }
+
+                OverlapPolicy::CancelPrevious => {
+                    #[cfg(feature = "logging")]
+                    tracing::warn!("cancelled previous run of job at {:?}", scheduled);
+
+                    for (_, handle) in self.in_flight.remove(&key).into_iter().flatten() {
+                        handle.abort();
                     }
 
-                    continue;
+                    if task.deferred > 0 {
+                        task.deferred = 0;
+                        self.deferred.retain(|(deferred, _)| *deferred != key);
+                    }
                 }
+            }
+        }
+
+        if limited {
+            #[cfg(feature = "logging")]
+            tracing::debug!(
+                "deferred job at {:?}, as a concurrency limit is reached",
+                scheduled
+            );
+
+            task.deferred += 1;
+            self.deferred.push_back((key, scheduled));
+            return;
+        }
+
+        self.run(key, scheduled, now);
+    }
+
+    /// Calls the command of a task for an occurrence.
+    fn run(&mut self, key: DefaultKey, scheduled: DateTime<Tz>, now: &DateTime<Tz>) {
+        let Some(task) = self.tasks.get_mut(key) else {
+            return;
+        };
+
+        let id = JobId(key);
+
+        self.subscribers.emit(SchedulerEvent::Fired {
+            id,
+            scheduled: scheduled.clone(),
+            actual: now.clone(),
+        });
 
-                next = Some((id, job.next.clone()));
+        match &task.command {
+            JobCommand::Sync(func) => {
+                func(id);
+                self.subscribers.emit(SchedulerEvent::Completed(id));
             }
 
-            if let Some((id, date)) = next {
-                let seconds_until = date.signed_duration_since(Tz::now()).num_seconds();
-                if let Ok(seconds_until) = u64::try_from(seconds_until) {
-                    if seconds_until > 0 {
-                        #[cfg(feature = "logging")]
-                        tracing::info!("next job in {} seconds", seconds_until);
+            JobCommand::Async(func) => {
+                let number = self.runs;
+                self.runs += 1;
+
+                let (handle, registration) = AbortHandle::new_pair();
+                self.in_flight
+                    .entry(key)
+                    .or_default()
+                    .push((number, handle));
+
+                let pool = task.job.pool.clone();
+                self.active.0 += 1;
+                if let Some(pool) = &pool {
+                    *self.active.1.entry(pool.clone()).or_default() += 1;
+                }
+
+                let command = Abortable::new(func(id), registration);
+
+                // The timeout is measured from the start of the run, rather than the occurrence.
+                let timeout = task
+                    .job
+                    .timeout
+                    .and_then(|timeout| chrono::Duration::from_std(timeout).ok())
+                    .and_then(|timeout| self.clock.now().checked_add_signed(timeout))
+                    .map(|deadline| self.clock.sleep_until(deadline));
+
+                let run = async move {
+                    let result = match timeout {
+                        Some(timeout) => {
+                            match futures::future::select(command, Box::pin(timeout)).await {
+                                Either::Left((result, _)) => Some(result),
+                                Either::Right(_) => None,
+                            }
+                        }
+                        None => Some(command.await),
+                    };
 
-                        let duration = Duration::from_secs(seconds_until);
-                        self.next = Some((id, duration));
-                        return;
+                    let outcome = match result {
+                        Some(Ok(())) => Outcome::Completed,
+                        Some(Err(_aborted)) => Outcome::Cancelled,
+                        None => Outcome::TimedOut,
+                    };
+
+                    Run {
+                        id,
+                        pool,
+                        number,
+                        outcome,
                     }
+                };
+
+                self.running.push(run.boxed());
+            }
+
+            JobCommand::Stream(ticks) => {
+                ticks.send(scheduled);
+                self.subscribers.emit(SchedulerEvent::Completed(id));
+            }
+        }
+    }
+
+    /// Handles a run of an async command which has finished, starting any deferred or queued runs.
+    fn finished(&mut self, run: Run) {
+        let event = match run.outcome {
+            Outcome::Completed => SchedulerEvent::Completed(run.id),
+            Outcome::Cancelled => SchedulerEvent::Cancelled(run.id),
+            Outcome::TimedOut => {
+                #[cfg(feature = "logging")]
+                tracing::warn!("cancelled run of job, as it timed out");
+
+                SchedulerEvent::TimedOut(run.id)
+            }
+        };
+
+        self.subscribers.emit(event);
+
+        self.active.0 -= 1;
+        if let Some(pool) = &run.pool {
+            if let Some(active) = self.active.1.get_mut(pool) {
+                *active -= 1;
+                if *active == 0 {
+                    self.active.1.remove(pool);
+                }
+            }
+        }
+
+        self.start_deferred();
+
+        if let Some(runs) = self.in_flight.get_mut(&run.id.0) {
+            runs.retain(|(number, _)| *number != run.number);
+            if runs.is_empty() {
+                self.in_flight.remove(&run.id.0);
+            }
+        }
+
+        let idle = !self.in_flight.contains_key(&run.id.0);
+
+        if let Some(task) = self.tasks.get_mut(run.id.0) {
+            // Queued occurrences are not started once the job is paused or the service is stopping.
+            if idle && !task.paused && self.stopping.is_none() {
+                if let Some(scheduled) = task.queued.take() {
+                    let now = self.now();
+                    self.fire(run.id.0, scheduled, &now);
+                }
+            }
+        }
+
+        self.next();
+    }
+
+    /// Whether an async command in the given pool may start without exceeding a concurrency limit.
+    fn has_slot(&self, pool: Option<&str>) -> bool {
+        let (active, pools) = &self.active;
+
+        let global = self
+            .config
+            .max_concurrency
+            .is_none_or(|limit| *active < limit);
+
+        let pool = pool.is_none_or(|pool| {
+            self.config
+                .pools
+                .get(pool)
+                .is_none_or(|limit| pools.get(pool).copied().unwrap_or(0) < *limit)
+        });
+
+        global && pool
+    }
+
+    /// Starts deferred occurrences, in the order they fired, while the concurrency limits allow.
+    fn start_deferred(&mut self) {
+        if self.stopping.is_some() {
+            return;
+        }
+
+        let mut index = 0;
+
+        while let Some((key, _)) = self.deferred.get(index) {
+            let pool = match self.tasks.get(*key) {
+                Some(task) => task.job.pool.as_deref(),
+                // The task was removed, so its occurrence is discarded.
+                None => {
+                    self.deferred.remove(index);
+                    continue;
                 }
+            };
 
-                self.call(id);
+            if !self.has_slot(pool) {
+                if !self.has_slot(None) {
+                    break;
+                }
+
+                // Another pool may still have room.
+                index += 1;
                 continue;
             }
 
+            if let Some((key, scheduled)) = self.deferred.remove(index) {
+                if let Some(task) = self.tasks.get_mut(key) {
+                    task.deferred -= 1;
+                }
+
+                let now = self.now();
+                self.run(key, scheduled, &now);
+            }
+        }
+    }
+
+    /// Removes a task which has no occurrences left.
+    fn finish(&mut self, key: DefaultKey) {
+        self.tasks.remove(key);
+        self.subscribers.emit(SchedulerEvent::Exhausted(JobId(key)));
+        self.jobs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(key);
+    }
+
+    /// Whether a shutdown has finished waiting for running commands.
+    fn has_stopped(&self) -> bool {
+        self.stopping.as_ref().is_some_and(|stopping| {
+            self.running.is_empty()
+                || stopping
+                    .deadline
+                    .is_some_and(|deadline| self.clock.now() >= deadline)
+        })
+    }
+
+    fn shutdown(&mut self, mode: ShutdownMode, reply: oneshot::Sender<()>) {
+        #[cfg(feature = "logging")]
+        tracing::info!("shutting down: {:?}", mode);
+
+        let deadline = match mode {
+            ShutdownMode::Now => Some(self.clock.now()),
+            ShutdownMode::Drain => None,
+            ShutdownMode::Deadline(timeout) => chrono::Duration::from_std(timeout)
+                .ok()
+                .and_then(|timeout| self.clock.now().checked_add_signed(timeout)),
+        };
+
+        let stopping = self.stopping.get_or_insert(Stopping {
+            deadline,
+            replies: Vec::new(),
+        });
+
+        // A later request may only bring the deadline forward.
+        stopping.deadline = match (stopping.deadline, deadline) {
+            (Some(current), Some(deadline)) => Some(current.min(deadline)),
+            (current, deadline) => current.or(deadline),
+        };
+
+        stopping.replies.push(reply);
+        self.next = None;
+    }
+
+    pub fn next(&mut self) {
+        self.next = None;
+
+        if self.stopping.is_some() {
             return;
         }
+
+        while let Some(Reverse((date, key))) = self.queue.peek().cloned() {
+            let scheduled = self
+                .tasks
+                .get(key)
+                .is_some_and(|task| task.is_queued_at(&date));
+
+            if !scheduled {
+                self.queue.pop();
+                continue;
+            }
+
+            let now = self.now();
+
+            if date > now.with_timezone(&Utc) {
+                #[cfg(feature = "logging")]
+                tracing::info!("next job at {:?}", date);
+
+                self.next = Some((key, date));
+                return;
+            }
+
+            self.queue.pop();
+            self.call(key, &now);
+        }
+    }
+
+    fn reschedule(&mut self, key: DefaultKey, job: Job<Tz>) {
+        let now = self.now();
+
+        let Some(task) = self.tasks.get_mut(key) else {
+            return;
+        };
+
+        match job.first(&now) {
+            Some(next) => {
+                if !task.paused {
+                    self.queue.push(Reverse((next.with_timezone(&Utc), key)));
+                }
+
+                task.job = job;
+                task.next = next;
+            }
+
+            None => self.finish(key),
+        }
+
+        self.compact();
+        self.next();
+    }
+
+    fn resume(&mut self, key: DefaultKey, mode: ResumeMode) {
+        let now = self.now();
+
+        let Some(task) = self.tasks.get_mut(key) else {
+            return;
+        };
+
+        if !task.paused {
+            return;
+        }
+
+        task.paused = false;
+
+        if mode == ResumeMode::SkipMissed && task.next <= now {
+            match task.job.next_after(&now) {
+                Some(next) => task.next = next,
+                None => {
+                    self.finish(key);
+                    return;
+                }
+            }
+        }
+
+        self.queue
+            .push(Reverse((task.next.with_timezone(&Utc), key)));
+        self.compact();
+        self.next();
+    }
+
+    /// Discards stale queue entries once they outnumber the scheduled tasks.
+    fn compact(&mut self) {
+        if self.queue.len() > 2 * self.tasks.len() + 16 {
+            let tasks = &self.tasks;
+            self.queue.retain(|Reverse((date, key))| {
+                tasks.get(*key).is_some_and(|task| task.is_queued_at(date))
+            });
+        }
     }
 
     pub fn update(&mut self, message: SchedMessage<Tz>) {
         match message {
-            SchedMessage::Insert(id, job, func) => {
-                self.tasks.insert(id.0, (job, func));
+            SchedMessage::Insert(id, job, command) => {
+                self.subscribers.emit(SchedulerEvent::Inserted(id));
+
+                if let Some(next) = job.first(&self.now()) {
+                    let job = *job;
+                    self.queue.push(Reverse((next.with_timezone(&Utc), id.0)));
+                    self.tasks.insert(
+                        id.0,
+                        Task {
+                            job,
+                            command,
+                            next,
+                            paused: false,
+                            queued: None,
+                            deferred: 0,
+                        },
+                    );
+                } else {
+                    self.finish(id.0);
+                }
+
                 self.next();
             }
 
             SchedMessage::Remove(id) => {
-                self.tasks.remove(id.0);
+                if self.tasks.remove(id.0).is_some() {
+                    self.subscribers.emit(SchedulerEvent::Removed(id));
+                }
+
+                self.compact();
                 self.next();
             }
+
+            SchedMessage::Reschedule(id, job) => self.reschedule(id.0, *job),
+
+            SchedMessage::ReplaceCommand(id, command) => {
+                if let Some(task) = self.tasks.get_mut(id.0) {
+                    // Replacing the sender would end the stream while the job kept firing.
+                    if let JobCommand::Stream(_) = task.command {
+                        #[cfg(feature = "logging")]
+                        tracing::warn!("kept the command of a streamed job");
+                    } else {
+                        task.command = command;
+                    }
+                }
+            }
+
+            SchedMessage::Pause(id) => {
+                if let Some(task) = self.tasks.get_mut(id.0) {
+                    task.paused = true;
+                    task.queued = None;
+
+                    if task.deferred > 0 {
+                        task.deferred = 0;
+                        self.deferred.retain(|(deferred, _)| *deferred != id.0);
+                    }
+
+                    self.next();
+                }
+            }
+
+            SchedMessage::Resume(id, mode) => self.resume(id.0, mode),
+
+            SchedMessage::CancelRunning(id) => {
+                // Runs are forgotten once their futures report being cancelled.
+                for (_, handle) in self.in_flight.get(&id.0).into_iter().flatten() {
+                    handle.abort();
+                }
+            }
+
+            SchedMessage::NextRun(id, reply) => {
+                let next = self
+                    .tasks
+                    .get(id.0)
+                    .filter(|task| !task.paused)
+                    .map(|task| task.next.clone());
+
+                let _res = reply.send(next);
+            }
+
+            SchedMessage::List(reply) => {
+                let mut jobs = self
+                    .tasks
+                    .iter()
+                    .map(|(key, task)| JobInfo {
+                        id: JobId(key),
+                        name: task.job.name.clone(),
+                        next: task.next.clone(),
+                        paused: task.paused,
+                    })
+                    .collect::<Vec<_>>();
+
+                jobs.sort_by(|a, b| a.next.cmp(&b.next));
+                let _res = reply.send(jobs);
+            }
+
+            SchedMessage::Shutdown(mode, reply) => self.shutdown(mode, reply),
+
+            SchedMessage::Subscribe(sender) => self.subscribers.add(sender),
         }
     }
 }
@@ -0,0 +1,153 @@
+// Copyright 2021 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::scheduler::JobRemover;
+use crate::{JobId, TimeZoneExt};
+use chrono::DateTime;
+use futures::Stream;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+/// How a [`JobStream`] buffers occurrences which its consumer has yet to receive.
+///
+/// Bounded buffers must have a capacity of at least one, or no occurrence
+/// could ever be received.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StreamBuffer {
+    /// Buffer every occurrence, however far behind the consumer falls.
+    #[default]
+    Unbounded,
+    /// Buffer up to the given number of occurrences, dropping new ones while full.
+    DropNewest(usize),
+    /// Buffer up to the given number of occurrences, dropping the oldest to make room.
+    ///
+    /// With a capacity of one, the consumer only ever sees the latest occurrence.
+    DropOldest(usize),
+}
+
+/// A stream of the occurrences of a job, created with [`crate::Scheduler::stream`].
+///
+/// Each item is the ID of the job with the time that the occurrence was
+/// scheduled for. The stream ends once the job has no occurrences left, or
+/// once the scheduler service exits. Dropping the stream removes the job.
+pub struct JobStream<Tz: TimeZoneExt> {
+    id: JobId,
+    ticks: Arc<Mutex<Ticks<Tz>>>,
+    remover: JobRemover,
+}
+
+impl<Tz: TimeZoneExt> JobStream<Tz> {
+    pub(crate) fn new(id: JobId, ticks: Arc<Mutex<Ticks<Tz>>>, remover: JobRemover) -> Self {
+        Self { id, ticks, remover }
+    }
+
+    /// The ID of the job which this stream receives occurrences of.
+    #[must_use]
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+}
+
+impl<Tz: TimeZoneExt> Stream for JobStream<Tz> {
+    type Item = (JobId, DateTime<Tz>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut ticks = lock(&self.ticks);
+
+        if let Some(date) = ticks.queue.pop_front() {
+            return Poll::Ready(Some((self.id, date)));
+        }
+
+        if ticks.closed {
+            return Poll::Ready(None);
+        }
+
+        ticks.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<Tz: TimeZoneExt> Drop for JobStream<Tz> {
+    fn drop(&mut self) {
+        self.remover.remove(self.id);
+    }
+}
+
+/// Occurrences waiting to be received by a [`JobStream`].
+pub(crate) struct Ticks<Tz: TimeZoneExt> {
+    buffer: StreamBuffer,
+    queue: VecDeque<DateTime<Tz>>,
+    waker: Option<Waker>,
+    /// Set once the service will send no more occurrences.
+    closed: bool,
+}
+
+impl<Tz: TimeZoneExt> Ticks<Tz> {
+    pub fn new(buffer: StreamBuffer) -> Self {
+        if let StreamBuffer::DropNewest(capacity) | StreamBuffer::DropOldest(capacity) = buffer {
+            assert!(
+                capacity > 0,
+                "stream buffer capacity must be greater than zero"
+            );
+        }
+
+        Self {
+            buffer,
+            queue: VecDeque::new(),
+            waker: None,
+            closed: false,
+        }
+    }
+}
+
+/// The service's end of a [`JobStream`], which closes the stream when dropped.
+pub(crate) struct TickSender<Tz: TimeZoneExt>(pub Arc<Mutex<Ticks<Tz>>>);
+
+impl<Tz: TimeZoneExt> TickSender<Tz> {
+    /// Sends an occurrence to the stream, as permitted by its buffer.
+    pub fn send(&self, date: DateTime<Tz>) {
+        let mut ticks = lock(&self.0);
+
+        let full = match ticks.buffer {
+            StreamBuffer::Unbounded => false,
+            StreamBuffer::DropNewest(capacity) | StreamBuffer::DropOldest(capacity) => {
+                ticks.queue.len() >= capacity
+            }
+        };
+
+        if full {
+            #[cfg(feature = "logging")]
+            tracing::debug!("job stream lagging, dropped an occurrence");
+
+            match ticks.buffer {
+                StreamBuffer::DropOldest(_) => {
+                    ticks.queue.pop_front();
+                }
+                _ => return,
+            }
+        }
+
+        ticks.queue.push_back(date);
+
+        if let Some(waker) = ticks.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<Tz: TimeZoneExt> Drop for TickSender<Tz> {
+    fn drop(&mut self) {
+        let mut ticks = lock(&self.0);
+        ticks.closed = true;
+
+        if let Some(waker) = ticks.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
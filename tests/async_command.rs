@@ -0,0 +1,31 @@
+mod common;
+
+use async_cron_scheduler::Job;
+use common::{advance, at, count, launch, sleep};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn service_drives_async_commands() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let completed = Arc::new(AtomicUsize::new(0));
+
+    // Each run sleeps for 5 seconds, which only completes if the service polls it.
+    let job = Job::cron("0 * * * * *").unwrap();
+    let command = sleep(&clock, 5, count(&started), count(&completed));
+    pool.run_until(scheduler.insert_async(job, command))
+        .unwrap();
+    pool.run_until_stalled();
+
+    advance(&clock, &mut pool, 60);
+    assert_eq!(started.load(Ordering::SeqCst), 1);
+    assert_eq!(completed.load(Ordering::SeqCst), 0);
+
+    advance(&clock, &mut pool, 4);
+    assert_eq!(completed.load(Ordering::SeqCst), 0);
+
+    advance(&clock, &mut pool, 1);
+    assert_eq!(completed.load(Ordering::SeqCst), 1);
+}
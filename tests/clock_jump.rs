@@ -0,0 +1,55 @@
+mod common;
+
+use async_cron_scheduler::{Job, MockClock, SchedulerConfig, SchedulerHandle};
+use chrono::Utc;
+use common::{advance, at, launch_with, record, Fired};
+use futures::executor::LocalPool;
+use std::time::Duration;
+
+fn launch(config: SchedulerConfig) -> (MockClock, SchedulerHandle<Utc>, LocalPool, Fired) {
+    let (clock, scheduler, mut pool) = launch_with(at(0), config);
+
+    let fired = Fired::default();
+    let job = Job::cron("0 * * * * *").unwrap();
+    pool.run_until(scheduler.insert(job, record(&clock, &fired)))
+        .unwrap();
+    pool.run_until_stalled();
+
+    (clock, scheduler, pool, fired)
+}
+
+#[test]
+fn resync_notices_forward_jumps() {
+    let config = SchedulerConfig::default().resync(Duration::from_secs(10));
+    let (clock, _scheduler, mut pool, fired) = launch(config);
+
+    clock.set(at(55));
+    advance(&clock, &mut pool, 10);
+
+    assert_eq!(*fired.lock().unwrap(), [at(65)]);
+}
+
+#[test]
+fn sleeps_overshoot_forward_jumps_without_resync() {
+    let (clock, _scheduler, mut pool, fired) = launch(SchedulerConfig::default());
+
+    clock.set(at(55));
+    advance(&clock, &mut pool, 10);
+
+    assert!(fired.lock().unwrap().is_empty());
+}
+
+#[test]
+fn backward_jumps_do_not_fire_early() {
+    let (clock, _scheduler, mut pool, fired) = launch(SchedulerConfig::default());
+
+    advance(&clock, &mut pool, 30);
+    clock.set(at(0));
+
+    // The original sleep ends when the wall clock reads 30 seconds.
+    advance(&clock, &mut pool, 59);
+    assert!(fired.lock().unwrap().is_empty());
+
+    advance(&clock, &mut pool, 1);
+    assert_eq!(*fired.lock().unwrap(), [at(60)]);
+}
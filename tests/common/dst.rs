@@ -0,0 +1,71 @@
+//! Fixtures for the daylight saving time tests, which run through the
+//! transitions of central European time in 2024.
+
+use super::{record, Fired};
+use async_cron_scheduler::{DstPolicy, Job, MockClock, Scheduler, TimeZoneExt};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use futures::executor::LocalPool;
+use futures::task::LocalSpawnExt;
+use std::time::Duration;
+
+pub fn utc_at(month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, month, day)
+        .unwrap()
+        .and_hms_opt(hour, minute, 0)
+        .unwrap()
+}
+
+pub fn at(month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    utc_at(month, day, hour, minute).and_utc()
+}
+
+/// Runs the job from the given time, minute by minute, returning when it fired.
+pub fn fire_times<Tz>(
+    timezone: Tz,
+    job: Job<Tz>,
+    from: DateTime<Utc>,
+    minutes: u32,
+) -> Vec<DateTime<Utc>>
+where
+    Tz: TimeZoneExt + 'static,
+    Tz::Offset: Send + Sync,
+{
+    let clock = MockClock::new(from);
+    let (scheduler, service) = Scheduler::launch(timezone, clock.clone());
+
+    let mut pool = LocalPool::new();
+    pool.spawner().spawn_local(service).unwrap();
+
+    let fired = Fired::default();
+    pool.run_until(scheduler.insert(job, record(&clock, &fired)))
+        .unwrap();
+    pool.run_until_stalled();
+
+    for _ in 0..minutes {
+        clock.advance(Duration::from_secs(60));
+        pool.run_until_stalled();
+    }
+
+    let fired = fired.lock().unwrap().clone();
+    fired
+}
+
+/// Runs a job at 02:30 local time across the night that clocks go forward.
+pub fn spring_forward<Tz>(timezone: Tz, policy: DstPolicy) -> Vec<DateTime<Utc>>
+where
+    Tz: TimeZoneExt + 'static,
+    Tz::Offset: Send + Sync,
+{
+    let job = Job::<Tz>::cron("0 30 2 * * *").unwrap().dst(policy);
+    fire_times(timezone, job, at(3, 30, 12, 0), 48 * 60)
+}
+
+/// Runs a job at 02:30 local time across the night that clocks go back.
+pub fn fall_back<Tz>(timezone: Tz, policy: DstPolicy) -> Vec<DateTime<Utc>>
+where
+    Tz: TimeZoneExt + 'static,
+    Tz::Offset: Send + Sync,
+{
+    let job = Job::<Tz>::cron("0 30 2 * * *").unwrap().dst(policy);
+    fire_times(timezone, job, at(10, 26, 12, 0), 48 * 60)
+}
@@ -0,0 +1,126 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+pub mod dst;
+
+use async_cron_scheduler::{Clock, JobId, MockClock, Scheduler, SchedulerConfig, SchedulerHandle};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::executor::LocalPool;
+use futures::future::{BoxFuture, RemoteHandle};
+use futures::task::LocalSpawnExt;
+use futures::FutureExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Records of when commands fired.
+pub type Fired<T = DateTime<Utc>> = Arc<Mutex<Vec<T>>>;
+
+/// The given number of seconds after the start of 2024.
+pub fn at(second: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + chrono::Duration::seconds(second.into())
+}
+
+/// The number of seconds since the start of 2024, as the inverse of [`at`].
+pub fn second(date: DateTime<Utc>) -> i64 {
+    (date - at(0)).num_seconds()
+}
+
+/// A command which records when it fired.
+pub fn record(clock: &MockClock, fired: &Fired) -> impl Fn(JobId) + Send + Sync + 'static {
+    let (clock, fired) = (clock.clone(), fired.clone());
+    move |_| fired.lock().unwrap().push(clock.now())
+}
+
+/// A command which records which job fired, and when.
+pub fn record_id(
+    clock: &MockClock,
+    fired: &Fired<(JobId, DateTime<Utc>)>,
+) -> impl Fn(JobId) + Send + Sync + 'static {
+    let (clock, fired) = (clock.clone(), fired.clone());
+    move |id| fired.lock().unwrap().push((id, clock.now()))
+}
+
+/// A command which records when it fired under the given name.
+pub fn record_as(
+    name: &'static str,
+    clock: &MockClock,
+    fired: &Fired<(&'static str, DateTime<Utc>)>,
+) -> impl Fn(JobId) + Send + Sync + 'static {
+    let (clock, fired) = (clock.clone(), fired.clone());
+    move |_| fired.lock().unwrap().push((name, clock.now()))
+}
+
+/// An async command which sleeps on the clock for the given number of seconds,
+/// passing the time to `started` when it fires and to `completed` once it wakes.
+pub fn sleep(
+    clock: &MockClock,
+    seconds: i64,
+    started: impl Fn(DateTime<Utc>) + Send + Sync + 'static,
+    completed: impl Fn(DateTime<Utc>) + Send + Sync + 'static,
+) -> impl Fn(JobId) -> BoxFuture<'static, ()> + Send + Sync + 'static {
+    let (clock, completed) = (clock.clone(), Arc::new(completed));
+    move |_| {
+        let now = clock.now();
+        started(now);
+
+        let sleep = clock.sleep_until(now + chrono::Duration::seconds(seconds));
+        let (clock, completed) = (clock.clone(), completed.clone());
+        async move {
+            sleep.await;
+            completed(clock.now());
+        }
+        .boxed()
+    }
+}
+
+/// A callback for [`sleep`] which counts how many times it was called.
+pub fn count(counter: &Arc<AtomicUsize>) -> impl Fn(DateTime<Utc>) + Send + Sync + 'static {
+    let counter = counter.clone();
+    move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Launches a scheduler in UTC whose clock starts at the given time, with
+/// its service spawned on a local pool.
+pub fn launch(from: DateTime<Utc>) -> (MockClock, SchedulerHandle<Utc>, LocalPool) {
+    launch_with(from, SchedulerConfig::default())
+}
+
+/// Launches a scheduler as with [`launch`], using the given config.
+pub fn launch_with(
+    from: DateTime<Utc>,
+    config: SchedulerConfig,
+) -> (MockClock, SchedulerHandle<Utc>, LocalPool) {
+    let clock = MockClock::new(from);
+    let (scheduler, service) = Scheduler::launch_with(Utc, clock.clone(), config);
+
+    let pool = LocalPool::new();
+    pool.spawner().spawn_local(service).unwrap();
+
+    (clock, scheduler, pool)
+}
+
+/// Launches a scheduler as with [`launch`], returning a handle to its service
+/// which drops the service when it is dropped, and resolves once it exits.
+pub fn launch_remote(
+    from: DateTime<Utc>,
+) -> (MockClock, SchedulerHandle<Utc>, LocalPool, RemoteHandle<()>) {
+    let clock = MockClock::new(from);
+    let (scheduler, service) = Scheduler::launch(Utc, clock.clone());
+
+    let pool = LocalPool::new();
+    let service = pool.spawner().spawn_local_with_handle(service).unwrap();
+
+    (clock, scheduler, pool, service)
+}
+
+/// Lets the given number of seconds pass, one at a time.
+pub fn advance(clock: &MockClock, pool: &mut LocalPool, seconds: u32) {
+    for _ in 0..seconds {
+        clock.advance(Duration::from_secs(1));
+        pool.run_until_stalled();
+    }
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use async_cron_scheduler::{Job, SchedulerConfig};
+use common::{advance, at, launch_with, second, sleep, Fired};
+use std::time::Duration;
+
+/// Runs jobs every minute whose commands take 25 seconds, for the given number
+/// of seconds, returning which jobs started and when.
+fn run(
+    config: SchedulerConfig,
+    jobs: &[(&'static str, Option<&str>)],
+    seconds: u32,
+) -> Vec<(&'static str, i64)> {
+    let (clock, scheduler, mut pool) = launch_with(at(0), config);
+
+    let started = Fired::default();
+
+    for &(name, job_pool) in jobs {
+        let mut job = Job::every(Duration::from_secs(60));
+        if let Some(job_pool) = job_pool {
+            job = job.pool(job_pool);
+        }
+
+        let started = started.clone();
+        let record = move |now| started.lock().unwrap().push((name, second(now)));
+        let command = sleep(&clock, 25, record, |_| ());
+        pool.run_until(scheduler.insert_async(job, command))
+            .unwrap();
+    }
+    pool.run_until_stalled();
+
+    advance(&clock, &mut pool, seconds);
+
+    let started = started.lock().unwrap().clone();
+    started
+}
+
+#[test]
+fn unlimited() {
+    let jobs = [("a", None), ("b", None), ("c", None)];
+    assert_eq!(
+        run(SchedulerConfig::default(), &jobs, 30),
+        [("a", 0), ("b", 0), ("c", 0)]
+    );
+}
+
+#[test]
+fn max_concurrency() {
+    let config = SchedulerConfig::default().max_concurrency(1);
+    let jobs = [("a", None), ("b", None), ("c", None)];
+
+    // Occurrences which fired while the limit was reached start in the order they fired.
+    assert_eq!(
+        run(config, &jobs, 110),
+        [("a", 0), ("b", 25), ("c", 50), ("a", 75), ("b", 100)]
+    );
+}
+
+#[test]
+fn pools() {
+    let config = SchedulerConfig::default()
+        .pool("database", 1)
+        .pool("network", 2);
+    let jobs = [
+        ("a", Some("database")),
+        ("b", Some("database")),
+        ("c", Some("network")),
+        ("d", Some("network")),
+        ("e", None),
+    ];
+
+    assert_eq!(
+        run(config, &jobs, 30),
+        [("a", 0), ("c", 0), ("d", 0), ("e", 0), ("b", 25)]
+    );
+}
+
+#[test]
+fn pools_within_max_concurrency() {
+    let config = SchedulerConfig::default()
+        .max_concurrency(2)
+        .pool("database", 1);
+    let jobs = [
+        ("a", Some("database")),
+        ("b", Some("database")),
+        ("c", None),
+    ];
+
+    assert_eq!(run(config, &jobs, 30), [("a", 0), ("c", 0), ("b", 25)]);
+}
+
+#[test]
+fn unconfigured_pools_are_unlimited() {
+    let config = SchedulerConfig::default().pool("database", 1);
+    let jobs = [("a", Some("network")), ("b", Some("network"))];
+
+    assert_eq!(run(config, &jobs, 30), [("a", 0), ("b", 0)]);
+}
+
+#[test]
+#[should_panic(expected = "concurrency limit must be greater than zero")]
+fn rejects_a_zero_concurrency_limit() {
+    let _config = SchedulerConfig::default().max_concurrency(0);
+}
+
+#[test]
+#[should_panic(expected = "pool limit must be greater than zero")]
+fn rejects_a_zero_pool_limit() {
+    let _config = SchedulerConfig::default().pool("database", 0);
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use async_cron_scheduler::{Job, Schedule};
+use chrono::{DateTime, Utc};
+use common::{advance, at, launch, record, Fired};
+
+/// A schedule which fires on a fixed list of dates.
+struct Dates(Vec<DateTime<Utc>>);
+
+impl Schedule<Utc> for Dates {
+    fn next_after(&self, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.0.iter().find(|date| *date > after).copied()
+    }
+}
+
+#[test]
+fn fires_on_custom_schedule() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let fired = Fired::default();
+    let job = Job::new(Dates(vec![at(3), at(5), at(11)]));
+    pool.run_until(scheduler.insert(job, record(&clock, &fired)))
+        .unwrap();
+    pool.run_until_stalled();
+
+    advance(&clock, &mut pool, 15);
+
+    assert_eq!(*fired.lock().unwrap(), [at(3), at(5), at(11)]);
+}
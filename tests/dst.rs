@@ -0,0 +1,91 @@
+mod common;
+
+use async_cron_scheduler::{DstPolicy, Job, TimeZoneExt};
+use chrono::{FixedOffset, LocalResult, NaiveDate, NaiveDateTime, TimeZone};
+use common::dst::{at, fall_back, fire_times, spring_forward, utc_at};
+
+/// Central European time in 2024, with clocks going forward from 02:00 to
+/// 03:00 on the 31st of March, and back from 03:00 to 02:00 on the 27th of October.
+#[derive(Copy, Clone, Debug)]
+struct Berlin;
+
+impl Berlin {
+    fn offset_at(utc: &NaiveDateTime) -> FixedOffset {
+        let summer = utc >= &utc_at(3, 31, 1, 0) && utc < &utc_at(10, 27, 1, 0);
+        FixedOffset::east_opt(if summer { 7200 } else { 3600 }).unwrap()
+    }
+}
+
+impl TimeZone for Berlin {
+    type Offset = FixedOffset;
+
+    fn from_offset(_: &FixedOffset) -> Self {
+        Berlin
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+        self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        let offsets = [3600, 7200]
+            .map(|seconds| FixedOffset::east_opt(seconds).unwrap())
+            .into_iter()
+            .filter(|offset| Self::offset_at(&(*local - *offset)) == *offset)
+            .collect::<Vec<_>>();
+
+        match offsets[..] {
+            [offset] => LocalResult::Single(offset),
+            [winter, summer] => LocalResult::Ambiguous(summer, winter),
+            _ => LocalResult::None,
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+        Self::offset_at(&utc.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        Self::offset_at(utc)
+    }
+}
+
+impl TimeZoneExt for Berlin {}
+
+#[test]
+fn spring_forward_shift() {
+    let expected = [at(3, 31, 1, 30), at(4, 1, 0, 30)];
+    assert_eq!(spring_forward(Berlin, DstPolicy::Shift), expected);
+}
+
+#[test]
+fn spring_forward_skip() {
+    let expected = [at(4, 1, 0, 30)];
+    assert_eq!(spring_forward(Berlin, DstPolicy::Skip), expected);
+}
+
+#[test]
+fn fall_back_once() {
+    let expected = [at(10, 27, 0, 30), at(10, 28, 1, 30)];
+    assert_eq!(fall_back(Berlin, DstPolicy::Shift), expected);
+    assert_eq!(fall_back(Berlin, DstPolicy::Skip), expected);
+}
+
+#[test]
+fn fall_back_twice() {
+    let expected = [at(10, 27, 0, 30), at(10, 27, 1, 30), at(10, 28, 1, 30)];
+    assert_eq!(fall_back(Berlin, DstPolicy::Twice), expected);
+}
+
+#[test]
+fn fall_back_twice_keeps_intervals() {
+    let job = Job::<Berlin>::cron("0 */15 2 * * *")
+        .unwrap()
+        .dst(DstPolicy::Twice);
+    let fired = fire_times(Berlin, job, at(10, 26, 23, 0), 4 * 60);
+
+    let expected = (0..8)
+        .map(|quarter| at(10, 27, 0, 0) + chrono::Duration::minutes(15 * quarter))
+        .collect::<Vec<_>>();
+    assert_eq!(fired, expected);
+}
@@ -0,0 +1,29 @@
+//! Daylight saving time in the system's local time zone.
+//!
+//! The zone is chosen by setting `TZ`, which is only safe while no other
+//! thread reads the environment, so this binary holds a single test.
+
+mod common;
+
+use async_cron_scheduler::DstPolicy;
+use chrono::Local;
+use common::dst::{at, fall_back, spring_forward};
+
+#[test]
+fn local_time() {
+    std::env::set_var("TZ", "Europe/Berlin");
+
+    assert_eq!(
+        spring_forward(Local, DstPolicy::Shift),
+        [at(3, 31, 1, 30), at(4, 1, 0, 30)]
+    );
+    assert_eq!(spring_forward(Local, DstPolicy::Skip), [at(4, 1, 0, 30)]);
+    assert_eq!(
+        fall_back(Local, DstPolicy::Shift),
+        [at(10, 27, 0, 30), at(10, 28, 1, 30)]
+    );
+    assert_eq!(
+        fall_back(Local, DstPolicy::Twice),
+        [at(10, 27, 0, 30), at(10, 27, 1, 30), at(10, 28, 1, 30)]
+    );
+}
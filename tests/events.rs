@@ -0,0 +1,111 @@
+mod common;
+
+use async_cron_scheduler::{Job, MisfirePolicy, SchedulerEvent};
+use chrono::Utc;
+use common::{at, launch};
+use futures::executor::LocalPool;
+use futures::stream::Stream;
+use futures::{FutureExt, StreamExt};
+use std::time::Duration;
+
+/// Collects the events which have been delivered so far.
+fn drain(
+    pool: &mut LocalPool,
+    events: &mut (impl Stream<Item = SchedulerEvent<Utc>> + Unpin),
+) -> Vec<SchedulerEvent<Utc>> {
+    pool.run_until_stalled();
+
+    let mut drained = Vec::new();
+    while let Some(Some(event)) = events.next().now_or_never() {
+        drained.push(event);
+    }
+
+    drained
+}
+
+#[test]
+fn lifecycle() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+    let mut events = pool.run_until(scheduler.subscribe());
+
+    let once = pool
+        .run_until(scheduler.insert(Job::after(Duration::from_secs(1)), |_| ()))
+        .unwrap();
+    let every = pool
+        .run_until(scheduler.insert_async(Job::every(Duration::from_secs(2)), |_| async {}))
+        .unwrap();
+
+    assert_eq!(
+        drain(&mut pool, &mut events),
+        [
+            SchedulerEvent::Inserted(once),
+            SchedulerEvent::Inserted(every),
+            SchedulerEvent::Fired {
+                id: every,
+                scheduled: at(0),
+                actual: at(0)
+            },
+            SchedulerEvent::Completed(every),
+        ]
+    );
+
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(
+        drain(&mut pool, &mut events),
+        [
+            SchedulerEvent::Fired {
+                id: once,
+                scheduled: at(1),
+                actual: at(1)
+            },
+            SchedulerEvent::Completed(once),
+            SchedulerEvent::Exhausted(once),
+        ]
+    );
+
+    pool.run_until(scheduler.remove(every)).unwrap();
+    assert_eq!(
+        drain(&mut pool, &mut events),
+        [SchedulerEvent::Removed(every)]
+    );
+}
+
+#[test]
+fn skipped() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+    let mut events = pool.run_until(scheduler.subscribe());
+
+    let job = Job::cron("0 * * * * *")
+        .unwrap()
+        .misfire(MisfirePolicy::Skip);
+    let id = pool.run_until(scheduler.insert(job, |_| ())).unwrap();
+    drain(&mut pool, &mut events);
+
+    clock.advance(Duration::from_secs(90));
+    assert_eq!(
+        drain(&mut pool, &mut events),
+        [SchedulerEvent::Skipped {
+            id,
+            scheduled: at(60)
+        }]
+    );
+
+    // Every occurrence missed while suspended is reported.
+    clock.advance(Duration::from_secs(630));
+    let skipped = (2..=12)
+        .map(|minute| SchedulerEvent::Skipped {
+            id,
+            scheduled: at(minute * 60),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(drain(&mut pool, &mut events), skipped);
+}
+
+#[test]
+fn stream_ends_with_the_service() {
+    let (_clock, scheduler, mut pool) = launch(at(0));
+    let mut events = pool.run_until(scheduler.subscribe());
+
+    drop(scheduler);
+    assert_eq!(pool.run_until(events.next()), None);
+}
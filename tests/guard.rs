@@ -0,0 +1,65 @@
+mod common;
+
+use async_cron_scheduler::Job;
+use common::{advance, at, launch};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn dropping_removes_the_job() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let job = Job::cron("* * * * * *").unwrap();
+    let guard = pool
+        .run_until(scheduler.insert_guarded(job, {
+            let calls = calls.clone();
+            move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+            }
+        }))
+        .unwrap();
+    pool.run_until_stalled();
+
+    advance(&clock, &mut pool, 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let id = guard.id();
+    drop(guard);
+    assert!(!scheduler.contains(id));
+
+    pool.run_until_stalled();
+    advance(&clock, &mut pool, 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(pool.run_until(scheduler.list()).is_empty());
+}
+
+#[test]
+fn detached_jobs_stay_scheduled() {
+    let (_clock, scheduler, mut pool) = launch(at(0));
+
+    let job = Job::cron("* * * * * *").unwrap();
+    let guard = pool
+        .run_until(scheduler.insert_guarded(job, |_| ()))
+        .unwrap();
+    let id = guard.detach();
+    pool.run_until_stalled();
+
+    assert!(scheduler.contains(id));
+    assert!(pool.run_until(scheduler.next_run(id)).is_some());
+}
+
+#[test]
+fn outliving_the_service() {
+    let (_clock, scheduler, mut pool) = launch(at(0));
+
+    let job = Job::cron("* * * * * *").unwrap();
+    let guard = pool
+        .run_until(scheduler.insert_guarded(job, |_| ()))
+        .unwrap();
+
+    // Guards do not keep the service running.
+    drop(scheduler);
+    pool.run_until_stalled();
+    drop(guard);
+}
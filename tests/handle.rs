@@ -0,0 +1,43 @@
+mod common;
+
+use async_cron_scheduler::{Job, SchedulerHandle};
+use chrono::Utc;
+use common::{at, launch_remote};
+use futures::FutureExt;
+
+#[test]
+fn clones_share_the_service() {
+    let (_clock, scheduler, mut pool, mut service) = launch_remote(at(0));
+
+    // Insert from several tasks at once.
+    let inserts = (0..4).map(|_| {
+        let scheduler = scheduler.clone();
+        async move {
+            let job = Job::cron("* * * * * *").unwrap();
+            scheduler.insert(job, |_| ()).await.unwrap()
+        }
+    });
+    let ids = pool.run_until(futures::future::join_all(inserts));
+    pool.run_until_stalled();
+
+    assert_eq!(scheduler.len(), 4);
+    assert!(ids.iter().all(|&id| scheduler.contains(id)));
+
+    let handle = scheduler.clone();
+    pool.run_until(handle.remove(ids[0])).unwrap();
+    assert!(!scheduler.contains(ids[0]));
+
+    drop(scheduler);
+    pool.run_until_stalled();
+    assert_eq!((&mut service).now_or_never(), None);
+
+    drop(handle);
+    pool.run_until_stalled();
+    assert_eq!(service.now_or_never(), Some(()));
+}
+
+#[test]
+fn handles_are_send_and_sync() {
+    fn assert_send_sync<T: Clone + Send + Sync>() {}
+    assert_send_sync::<SchedulerHandle<Utc>>();
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use async_cron_scheduler::Job;
+use chrono::{DateTime, Utc};
+use common::{advance, at, launch, record, Fired};
+use std::time::Duration;
+
+fn run(job: Job<Utc>, seconds: u32) -> Vec<DateTime<Utc>> {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let fired = Fired::default();
+    pool.run_until(scheduler.insert(job, record(&clock, &fired)))
+        .unwrap();
+    pool.run_until_stalled();
+
+    advance(&clock, &mut pool, seconds);
+
+    let fired = fired.lock().unwrap().clone();
+    fired
+}
+
+#[test]
+fn fires_every_interval_across_minutes() {
+    let fired = run(Job::every(Duration::from_secs(90)), 300);
+    assert_eq!(fired, [at(0), at(90), at(180), at(270)]);
+}
+
+#[test]
+fn starts_at_the_offset() {
+    let job = Job::every(Duration::from_secs(7 * 60)).starting_in(Duration::from_secs(30));
+    let fired = run(job, 15 * 60);
+    assert_eq!(fired, [at(30), at(7 * 60 + 30), at(14 * 60 + 30)]);
+}
+
+#[test]
+fn stops_after_the_end() {
+    let job = Job::every(Duration::from_secs(2))
+        .starting_in(Duration::from_secs(5))
+        .until(at(10));
+    let fired = run(job, 20);
+    assert_eq!(fired, [at(5), at(7), at(9)]);
+}
+
+#[test]
+#[should_panic(expected = "job interval must be greater than zero")]
+fn rejects_a_zero_interval() {
+    let _job = Job::<Utc>::every(Duration::ZERO);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use async_cron_scheduler::{Job, MisfirePolicy};
+use chrono::{DateTime, Utc};
+use common::{at, launch};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Suspends a minutely job for ten and a half minutes, returning the
+/// number of times it fired on wake and when it will fire next.
+fn suspend(policy: MisfirePolicy) -> (usize, Option<DateTime<Utc>>) {
+    suspend_job(Job::cron("0 * * * * *").unwrap().misfire(policy))
+}
+
+/// Suspends the job for ten and a half minutes, returning the number of
+/// times it fired and when it will fire next.
+fn suspend_job(job: Job<Utc>) -> (usize, Option<DateTime<Utc>>) {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let id = pool
+        .run_until(scheduler.insert(job, {
+            let calls = calls.clone();
+            move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+            }
+        }))
+        .unwrap();
+    pool.run_until_stalled();
+
+    clock.advance(Duration::from_secs(630));
+    pool.run_until_stalled();
+
+    let next = pool.run_until(scheduler.next_run(id));
+    (calls.load(Ordering::SeqCst), next)
+}
+
+#[test]
+fn fire_all() {
+    assert_eq!(suspend(MisfirePolicy::FireAll), (10, Some(at(660))));
+}
+
+#[test]
+fn fire_once() {
+    assert_eq!(suspend(MisfirePolicy::FireOnce), (1, Some(at(660))));
+}
+
+#[test]
+fn skip() {
+    assert_eq!(suspend(MisfirePolicy::Skip), (0, Some(at(660))));
+}
+
+#[test]
+fn grace() {
+    let policy = MisfirePolicy::Grace(Duration::from_secs(120));
+    assert_eq!(suspend(policy), (2, Some(at(660))));
+}
+
+#[test]
+fn intervals_stay_on_schedule() {
+    let every = |policy| Job::every(Duration::from_secs(60)).misfire(policy);
+
+    // Each includes the occurrence at the start.
+    assert_eq!(
+        suspend_job(every(MisfirePolicy::FireAll)),
+        (11, Some(at(660)))
+    );
+    assert_eq!(
+        suspend_job(every(MisfirePolicy::FireOnce)),
+        (2, Some(at(660)))
+    );
+    assert_eq!(suspend_job(every(MisfirePolicy::Skip)), (1, Some(at(660))));
+}
+
+#[test]
+fn on_time_occurrences_are_not_missed() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let job = Job::cron("* * * * * *")
+        .unwrap()
+        .misfire(MisfirePolicy::Skip);
+    pool.run_until(scheduler.insert(job, {
+        let calls = calls.clone();
+        move |_| {
+            calls.fetch_add(1, Ordering::SeqCst);
+        }
+    }))
+    .unwrap();
+    pool.run_until_stalled();
+
+    for _ in 0..5 {
+        clock.advance(Duration::from_millis(1500));
+        pool.run_until_stalled();
+    }
+
+    assert_eq!(calls.load(Ordering::SeqCst), 7);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use async_cron_scheduler::Job;
+use common::{advance, at, launch, record_id, Fired};
+use std::time::Duration;
+
+#[test]
+fn fires_jobs_in_order() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let fired = Fired::default();
+
+    let fizz = pool
+        .run_until(scheduler.insert(
+            Job::cron("*/2 * * * * *").unwrap(),
+            record_id(&clock, &fired),
+        ))
+        .unwrap();
+
+    let buzz = pool
+        .run_until(scheduler.insert(
+            Job::cron("*/3 * * * * *").unwrap(),
+            record_id(&clock, &fired),
+        ))
+        .unwrap();
+
+    pool.run_until_stalled();
+
+    advance(&clock, &mut pool, 6);
+
+    assert_eq!(
+        *fired.lock().unwrap(),
+        [
+            (fizz, at(2)),
+            (buzz, at(3)),
+            (fizz, at(4)),
+            (fizz, at(6)),
+            (buzz, at(6)),
+        ]
+    );
+}
+
+#[test]
+fn removed_jobs_stop_firing() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let fired = Fired::default();
+    let job = Job::cron("* * * * * *").unwrap();
+    let id = pool
+        .run_until(scheduler.insert(job, record_id(&clock, &fired)))
+        .unwrap();
+    pool.run_until_stalled();
+
+    advance(&clock, &mut pool, 1);
+
+    pool.run_until(scheduler.remove(id)).unwrap();
+    clock.advance(Duration::from_secs(5));
+    pool.run_until_stalled();
+
+    assert_eq!(*fired.lock().unwrap(), [(id, at(1))]);
+}
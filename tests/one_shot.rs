@@ -0,0 +1,39 @@
+mod common;
+
+use async_cron_scheduler::Job;
+use common::{advance, at, launch, record_id, Fired};
+use std::time::Duration;
+
+#[test]
+fn fires_once_at_and_after() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let fired = Fired::default();
+    let later = pool
+        .run_until(scheduler.insert(Job::at(at(5)), record_id(&clock, &fired)))
+        .unwrap();
+    let soon = pool
+        .run_until(scheduler.insert(
+            Job::after(Duration::from_secs(2)),
+            record_id(&clock, &fired),
+        ))
+        .unwrap();
+    pool.run_until_stalled();
+
+    advance(&clock, &mut pool, 10);
+
+    assert_eq!(*fired.lock().unwrap(), [(soon, at(2)), (later, at(5))]);
+}
+
+#[test]
+fn fires_immediately_when_already_passed() {
+    let (clock, scheduler, mut pool) = launch(at(10));
+
+    let fired = Fired::default();
+    let id = pool
+        .run_until(scheduler.insert(Job::at(at(5)), record_id(&clock, &fired)))
+        .unwrap();
+    pool.run_until_stalled();
+
+    assert_eq!(*fired.lock().unwrap(), [(id, at(10))]);
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use async_cron_scheduler::{
+    Job, JobId, MockClock, OverlapPolicy, SchedulerConfig, SchedulerHandle, ShutdownMode,
+};
+use chrono::Utc;
+use common::{advance, at, second, sleep, Fired};
+use futures::executor::LocalPool;
+use futures::task::LocalSpawnExt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Seconds = Fired<i64>;
+
+struct Harness {
+    clock: MockClock,
+    scheduler: SchedulerHandle<Utc>,
+    id: JobId,
+    pool: LocalPool,
+    /// When runs started.
+    started: Seconds,
+    /// When runs completed.
+    completed: Seconds,
+}
+
+impl Harness {
+    fn advance(&mut self, seconds: u32) {
+        advance(&self.clock, &mut self.pool, seconds);
+    }
+
+    fn results(&self) -> (Vec<i64>, Vec<i64>) {
+        let started = self.started.lock().unwrap().clone();
+        let completed = self.completed.lock().unwrap().clone();
+        (started, completed)
+    }
+}
+
+/// Launches a scheduler with a job every minute whose command takes 100 seconds.
+fn launch(policy: OverlapPolicy) -> Harness {
+    let (clock, scheduler, mut pool) = common::launch(at(0));
+
+    let started = Seconds::default();
+    let completed = Seconds::default();
+
+    let job = Job::every(Duration::from_secs(60)).overlap(policy);
+    let command = sleep(
+        &clock,
+        100,
+        {
+            let started = started.clone();
+            move |now| started.lock().unwrap().push(second(now))
+        },
+        {
+            let completed = completed.clone();
+            move |now| completed.lock().unwrap().push(second(now))
+        },
+    );
+    let id = pool
+        .run_until(scheduler.insert_async(job, command))
+        .unwrap();
+    pool.run_until_stalled();
+
+    Harness {
+        clock,
+        scheduler,
+        id,
+        pool,
+        started,
+        completed,
+    }
+}
+
+/// Runs the job for 250 seconds, returning when its runs started and completed.
+fn run(policy: OverlapPolicy) -> (Vec<i64>, Vec<i64>) {
+    let mut harness = launch(policy);
+    harness.advance(250);
+    harness.results()
+}
+
+#[test]
+fn allow() {
+    let (started, completed) = run(OverlapPolicy::Allow);
+    assert_eq!(started, [0, 60, 120, 180, 240]);
+    assert_eq!(completed, [100, 160, 220]);
+}
+
+#[test]
+fn skip() {
+    let (started, completed) = run(OverlapPolicy::Skip);
+    assert_eq!(started, [0, 120, 240]);
+    assert_eq!(completed, [100, 220]);
+}
+
+#[test]
+fn queue_one() {
+    let (started, completed) = run(OverlapPolicy::QueueOne);
+    assert_eq!(started, [0, 100, 200]);
+    assert_eq!(completed, [100, 200]);
+}
+
+#[test]
+fn cancel_previous() {
+    let (started, completed) = run(OverlapPolicy::CancelPrevious);
+    assert_eq!(started, [0, 60, 120, 180, 240]);
+    assert!(completed.is_empty());
+}
+
+#[test]
+fn queue_one_while_draining() {
+    let mut harness = launch(OverlapPolicy::QueueOne);
+
+    // The occurrence at 60 seconds is queued behind the first run.
+    harness.advance(70);
+
+    let done = Arc::new(Mutex::new(false));
+    harness
+        .pool
+        .spawner()
+        .spawn_local({
+            let (scheduler, done) = (harness.scheduler.clone(), done.clone());
+            async move {
+                scheduler.shutdown(ShutdownMode::Drain).await;
+                *done.lock().unwrap() = true;
+            }
+        })
+        .unwrap();
+
+    harness.advance(30);
+    assert!(*done.lock().unwrap());
+    assert_eq!(harness.results(), (vec![0], vec![100]));
+}
+
+#[test]
+fn queue_one_while_paused() {
+    let mut harness = launch(OverlapPolicy::QueueOne);
+    harness.advance(70);
+
+    harness
+        .pool
+        .run_until(harness.scheduler.pause(harness.id))
+        .unwrap();
+
+    harness.advance(180);
+    assert_eq!(harness.results(), (vec![0], vec![100]));
+}
+
+#[test]
+fn deferred_while_paused() {
+    let config = SchedulerConfig::default().max_concurrency(1);
+    let (clock, scheduler, mut pool) = common::launch_with(at(0), config);
+
+    let started = Fired::default();
+    let mut ids = Vec::new();
+    for name in ["a", "b"] {
+        let job = Job::every(Duration::from_secs(60));
+        let started = started.clone();
+        let command = sleep(
+            &clock,
+            25,
+            move |_| started.lock().unwrap().push(name),
+            |_| (),
+        );
+        let id = pool
+            .run_until(scheduler.insert_async(job, command))
+            .unwrap();
+        ids.push(id);
+    }
+    pool.run_until_stalled();
+
+    // The first occurrence of "b" is deferred behind "a", and paused before it starts.
+    pool.run_until(scheduler.pause(ids[1])).unwrap();
+    advance(&clock, &mut pool, 90);
+
+    assert_eq!(*started.lock().unwrap(), ["a", "a"]);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use async_cron_scheduler::{Job, ResumeMode};
+use chrono::{DateTime, Utc};
+use common::{advance, at, launch, record, Fired};
+use std::time::Duration;
+
+fn paused_for_three_seconds(mode: ResumeMode) -> Vec<DateTime<Utc>> {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let fired = Fired::default();
+    let job = Job::cron("* * * * * *").unwrap();
+    let id = pool
+        .run_until(scheduler.insert(job, record(&clock, &fired)))
+        .unwrap();
+    pool.run_until_stalled();
+
+    advance(&clock, &mut pool, 1);
+
+    pool.run_until(scheduler.pause(id)).unwrap();
+    assert_eq!(pool.run_until(scheduler.next_run(id)), None);
+
+    advance(&clock, &mut pool, 3);
+
+    let list = pool.run_until(scheduler.list());
+    assert!(list[0].paused);
+
+    pool.run_until(scheduler.resume(id, mode)).unwrap();
+    pool.run_until_stalled();
+
+    advance(&clock, &mut pool, 1);
+
+    let fired = fired.lock().unwrap().clone();
+    fired
+}
+
+#[test]
+fn resume_fires_missed() {
+    let fired = paused_for_three_seconds(ResumeMode::FireMissed);
+    assert_eq!(fired, [at(1), at(4), at(4), at(4), at(5)]);
+}
+
+#[test]
+fn resume_skips_missed() {
+    let fired = paused_for_three_seconds(ResumeMode::SkipMissed);
+    assert_eq!(fired, [at(1), at(5)]);
+}
+
+#[test]
+fn resumed_intervals_stay_on_schedule() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let fired = Fired::default();
+    let job = Job::every(Duration::from_secs(60));
+    let id = pool
+        .run_until(scheduler.insert(job, record(&clock, &fired)))
+        .unwrap();
+    pool.run_until_stalled();
+
+    pool.run_until(scheduler.pause(id)).unwrap();
+    advance(&clock, &mut pool, 90);
+
+    pool.run_until(scheduler.resume(id, ResumeMode::SkipMissed))
+        .unwrap();
+    assert_eq!(pool.run_until(scheduler.next_run(id)), Some(at(120)));
+
+    advance(&clock, &mut pool, 30);
+    assert_eq!(*fired.lock().unwrap(), [at(0), at(120)]);
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use async_cron_scheduler::Job;
+use common::{at, launch, record, Fired};
+use std::time::Duration;
+
+#[test]
+fn fires_at_the_scheduled_instant() {
+    let (clock, scheduler, mut pool) = launch(at(0) + chrono::Duration::milliseconds(250));
+
+    let fired = Fired::default();
+    let job = Job::cron("* * * * * *").unwrap();
+    pool.run_until(scheduler.insert(job, record(&clock, &fired)))
+        .unwrap();
+    pool.run_until_stalled();
+
+    // Less than a second remains, which must not be treated as overdue.
+    assert!(fired.lock().unwrap().is_empty());
+
+    clock.advance(Duration::from_millis(749));
+    pool.run_until_stalled();
+    assert!(fired.lock().unwrap().is_empty());
+
+    clock.advance(Duration::from_millis(1));
+    pool.run_until_stalled();
+    assert_eq!(*fired.lock().unwrap(), [at(1)]);
+
+    clock.advance(Duration::from_millis(999));
+    pool.run_until_stalled();
+    assert_eq!(fired.lock().unwrap().len(), 1);
+
+    clock.advance(Duration::from_millis(1));
+    pool.run_until_stalled();
+    assert_eq!(fired.lock().unwrap().last(), Some(&at(2)));
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use async_cron_scheduler::Job;
+use common::{at, launch};
+use std::time::Duration;
+
+#[test]
+fn reports_next_runs() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let fizz = pool
+        .run_until(scheduler.insert(Job::cron("*/2 * * * * *").unwrap(), |_| ()))
+        .unwrap();
+    let buzz = pool
+        .run_until(scheduler.insert(Job::cron("*/3 * * * * *").unwrap().name("buzz"), |_| ()))
+        .unwrap();
+
+    assert_eq!(pool.run_until(scheduler.next_run(fizz)), Some(at(2)));
+    assert_eq!(pool.run_until(scheduler.next_run(buzz)), Some(at(3)));
+
+    clock.advance(Duration::from_secs(2));
+    pool.run_until_stalled();
+
+    assert_eq!(pool.run_until(scheduler.next_run(fizz)), Some(at(4)));
+
+    let list = pool.run_until(scheduler.list());
+    let list = list
+        .iter()
+        .map(|job| (job.id, job.name.as_deref(), job.next))
+        .collect::<Vec<_>>();
+
+    assert_eq!(list, [(buzz, Some("buzz"), at(3)), (fizz, None, at(4))]);
+
+    pool.run_until(scheduler.remove(fizz)).unwrap();
+    assert_eq!(pool.run_until(scheduler.next_run(fizz)), None);
+    assert_eq!(pool.run_until(scheduler.list()).len(), 1);
+}
+
+#[test]
+fn forgets_finished_jobs() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let once = pool
+        .run_until(scheduler.insert(Job::after(Duration::from_secs(2)), |_| ()))
+        .unwrap();
+    let every = pool
+        .run_until(scheduler.insert(Job::cron("* * * * * *").unwrap(), |_| ()))
+        .unwrap();
+    pool.run_until_stalled();
+
+    assert_eq!(scheduler.len(), 2);
+    assert!(scheduler.contains(once));
+
+    clock.advance(Duration::from_secs(2));
+    pool.run_until_stalled();
+
+    assert_eq!(scheduler.len(), 1);
+    assert!(!scheduler.contains(once));
+    assert!(scheduler.contains(every));
+
+    pool.run_until(scheduler.remove(every)).unwrap();
+    assert!(scheduler.is_empty());
+}
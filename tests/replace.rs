@@ -0,0 +1,64 @@
+mod common;
+
+use async_cron_scheduler::{Job, ResumeMode, SchedulerError};
+use common::{advance, at, launch, record_as, Fired};
+
+#[test]
+fn reschedules_and_replaces_in_place() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let fired = Fired::default();
+    let job = Job::cron("*/5 * * * * *").unwrap();
+    let id = pool
+        .run_until(scheduler.insert(job, record_as("fizz", &clock, &fired)))
+        .unwrap();
+    pool.run_until_stalled();
+
+    pool.run_until(scheduler.reschedule(id, Job::cron("*/2 * * * * *").unwrap()))
+        .unwrap();
+    assert_eq!(pool.run_until(scheduler.next_run(id)), Some(at(2)));
+
+    advance(&clock, &mut pool, 2);
+
+    pool.run_until(scheduler.replace_command(id, record_as("buzz", &clock, &fired)))
+        .unwrap();
+
+    advance(&clock, &mut pool, 2);
+
+    assert_eq!(*fired.lock().unwrap(), [("fizz", at(2)), ("buzz", at(4))]);
+}
+
+#[test]
+fn unknown_jobs_are_reported() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let removed = pool
+        .run_until(scheduler.insert(Job::cron("* * * * * *").unwrap(), |_| ()))
+        .unwrap();
+    pool.run_until(scheduler.remove(removed)).unwrap();
+
+    let exhausted = pool
+        .run_until(scheduler.insert(Job::at(at(1)), |_| ()))
+        .unwrap();
+    pool.run_until_stalled();
+    advance(&clock, &mut pool, 1);
+
+    let unknown = Err(SchedulerError::UnknownJob);
+    for id in [removed, exhausted] {
+        let job = Job::cron("*/2 * * * * *").unwrap();
+        assert_eq!(pool.run_until(scheduler.reschedule(id, job)), unknown);
+        assert_eq!(
+            pool.run_until(scheduler.replace_command(id, |_| ())),
+            unknown
+        );
+        assert_eq!(
+            pool.run_until(scheduler.replace_command_async(id, |_| async {})),
+            unknown
+        );
+        assert_eq!(pool.run_until(scheduler.pause(id)), unknown);
+        assert_eq!(
+            pool.run_until(scheduler.resume(id, ResumeMode::FireMissed)),
+            unknown
+        );
+    }
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use async_cron_scheduler::{Job, ResumeMode, SchedulerError, ShutdownMode};
+
+#[test]
+fn stopped_service_is_reported() {
+    let (_clock, scheduler, mut pool, service) = common::launch_remote(common::at(0));
+
+    let id = pool
+        .run_until(scheduler.insert(Job::cron("* * * * * *").unwrap(), |_| ()))
+        .unwrap();
+    assert!(scheduler.is_running());
+
+    // Dropping the remote handle drops the service future.
+    drop(service);
+    pool.run_until_stalled();
+    assert!(!scheduler.is_running());
+
+    let job = Job::cron("* * * * * *").unwrap();
+    assert_eq!(
+        pool.run_until(scheduler.insert(job, |_| ())),
+        Err(SchedulerError::ServiceStopped)
+    );
+    assert_eq!(
+        pool.run_until(scheduler.remove(id)),
+        Err(SchedulerError::ServiceStopped)
+    );
+}
+
+#[test]
+fn requests_report_the_stopped_service() {
+    let (_clock, scheduler, mut pool) = common::launch(common::at(0));
+
+    let id = pool
+        .run_until(scheduler.insert(Job::cron("* * * * * *").unwrap(), |_| ()))
+        .unwrap();
+    pool.run_until(scheduler.shutdown(ShutdownMode::Now));
+    assert!(!scheduler.is_running());
+
+    let stopped = Err(SchedulerError::ServiceStopped);
+    let job = Job::cron("*/2 * * * * *").unwrap();
+    assert_eq!(pool.run_until(scheduler.reschedule(id, job)), stopped);
+    assert_eq!(
+        pool.run_until(scheduler.replace_command(id, |_| ())),
+        stopped
+    );
+    assert_eq!(
+        pool.run_until(scheduler.replace_command_async(id, |_| async {})),
+        stopped
+    );
+    assert_eq!(pool.run_until(scheduler.pause(id)), stopped);
+    assert_eq!(
+        pool.run_until(scheduler.resume(id, ResumeMode::FireMissed)),
+        stopped
+    );
+    assert_eq!(pool.run_until(scheduler.cancel_running(id)), stopped);
+    assert_eq!(pool.run_until(scheduler.remove(id)), stopped);
+}
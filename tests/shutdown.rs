@@ -0,0 +1,136 @@
+mod common;
+
+use async_cron_scheduler::{Job, MockClock, SchedulerError, SchedulerHandle, ShutdownMode};
+use chrono::Utc;
+use common::at;
+use futures::executor::LocalPool;
+use futures::task::LocalSpawnExt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+struct Harness {
+    clock: MockClock,
+    scheduler: SchedulerHandle<Utc>,
+    pool: LocalPool,
+    /// How many commands have started.
+    started: Arc<AtomicUsize>,
+    /// How many commands have run to completion.
+    completed: Arc<AtomicUsize>,
+}
+
+/// Launches a scheduler with a job which fires every second, taking ten seconds to complete.
+fn launch() -> Harness {
+    let (clock, scheduler, mut pool) = common::launch(at(0));
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let completed = Arc::new(AtomicUsize::new(0));
+
+    let job = Job::cron("* * * * * *").unwrap();
+    let command = common::sleep(
+        &clock,
+        10,
+        common::count(&started),
+        common::count(&completed),
+    );
+    pool.run_until(scheduler.insert_async(job, command))
+        .unwrap();
+    pool.run_until_stalled();
+
+    // Start one command.
+    clock.advance(Duration::from_secs(1));
+    pool.run_until_stalled();
+
+    Harness {
+        clock,
+        scheduler,
+        pool,
+        started,
+        completed,
+    }
+}
+
+/// Requests a shutdown, returning a flag that is set once it resolves.
+fn shutdown(harness: &mut Harness, mode: ShutdownMode) -> Arc<AtomicBool> {
+    let done = Arc::new(AtomicBool::new(false));
+    let scheduler = harness.scheduler.clone();
+
+    harness
+        .pool
+        .spawner()
+        .spawn_local({
+            let done = done.clone();
+            async move {
+                scheduler.shutdown(mode).await;
+                done.store(true, Ordering::SeqCst);
+            }
+        })
+        .unwrap();
+
+    harness.pool.run_until_stalled();
+    done
+}
+
+#[test]
+fn now() {
+    let mut harness = launch();
+    let done = shutdown(&mut harness, ShutdownMode::Now);
+
+    assert!(done.load(Ordering::SeqCst));
+    assert!(!harness.scheduler.is_running());
+    assert!(harness.scheduler.is_empty());
+
+    harness.clock.advance(Duration::from_secs(10));
+    harness.pool.run_until_stalled();
+    assert_eq!(harness.completed.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn drain() {
+    let mut harness = launch();
+    let done = shutdown(&mut harness, ShutdownMode::Drain);
+
+    for _ in 0..9 {
+        harness.clock.advance(Duration::from_secs(1));
+        harness.pool.run_until_stalled();
+        assert!(!done.load(Ordering::SeqCst));
+    }
+
+    harness.clock.advance(Duration::from_secs(1));
+    harness.pool.run_until_stalled();
+    assert!(done.load(Ordering::SeqCst));
+    assert!(harness.scheduler.is_empty());
+
+    // No jobs fired while draining.
+    assert_eq!(harness.started.load(Ordering::SeqCst), 1);
+    assert_eq!(harness.completed.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn deadline() {
+    let mut harness = launch();
+    let done = shutdown(&mut harness, ShutdownMode::Deadline(Duration::from_secs(5)));
+
+    harness.clock.advance(Duration::from_secs(4));
+    harness.pool.run_until_stalled();
+    assert!(!done.load(Ordering::SeqCst));
+
+    harness.clock.advance(Duration::from_secs(1));
+    harness.pool.run_until_stalled();
+    assert!(done.load(Ordering::SeqCst));
+    assert_eq!(harness.completed.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn refuses_requests_while_stopping() {
+    let mut harness = launch();
+    shutdown(&mut harness, ShutdownMode::Drain);
+
+    let job = Job::cron("* * * * * *").unwrap();
+    assert_eq!(
+        harness
+            .pool
+            .run_until(harness.scheduler.insert(job, |_| ())),
+        Err(SchedulerError::ServiceStopped)
+    );
+}
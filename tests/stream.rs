@@ -0,0 +1,103 @@
+mod common;
+
+use async_cron_scheduler::{Job, JobStream, StreamBuffer};
+use chrono::Utc;
+use common::{advance, at, launch};
+use futures::{FutureExt, StreamExt};
+use std::time::Duration;
+
+/// Collects the seconds of the occurrences which have been delivered so far.
+fn received(stream: &mut JobStream<Utc>) -> Vec<i64> {
+    let mut seconds = Vec::new();
+    while let Some(Some((id, date))) = stream.next().now_or_never() {
+        assert_eq!(id, stream.id());
+        seconds.push((date - at(0)).num_seconds());
+    }
+
+    seconds
+}
+
+fn buffered(buffer: StreamBuffer) -> Vec<i64> {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let job = Job::cron("* * * * * *").unwrap();
+    let mut stream = pool.run_until(scheduler.stream_with(job, buffer)).unwrap();
+    pool.run_until_stalled();
+    advance(&clock, &mut pool, 5);
+
+    received(&mut stream)
+}
+
+#[test]
+fn yields_scheduled_times() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let job = Job::every(Duration::from_secs(2));
+    let mut stream = pool.run_until(scheduler.stream(job)).unwrap();
+    pool.run_until_stalled();
+    assert_eq!(received(&mut stream), [0]);
+
+    advance(&clock, &mut pool, 6);
+    assert_eq!(received(&mut stream), [2, 4, 6]);
+}
+
+#[test]
+fn buffers_slow_consumers() {
+    assert_eq!(buffered(StreamBuffer::Unbounded), [1, 2, 3, 4, 5]);
+    assert_eq!(buffered(StreamBuffer::DropNewest(2)), [1, 2]);
+    assert_eq!(buffered(StreamBuffer::DropOldest(2)), [4, 5]);
+}
+
+#[test]
+fn dropping_removes_the_job() {
+    let (_clock, scheduler, mut pool) = launch(at(0));
+
+    let job = Job::cron("* * * * * *").unwrap();
+    let stream = pool.run_until(scheduler.stream(job)).unwrap();
+    let id = stream.id();
+    pool.run_until_stalled();
+    assert!(scheduler.contains(id));
+
+    drop(stream);
+    assert!(!scheduler.contains(id));
+
+    pool.run_until_stalled();
+    assert!(pool.run_until(scheduler.list()).is_empty());
+}
+
+#[test]
+fn ends_when_exhausted() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let job = Job::after(Duration::from_secs(1));
+    let mut stream = pool.run_until(scheduler.stream(job)).unwrap();
+    pool.run_until_stalled();
+    advance(&clock, &mut pool, 1);
+
+    assert_eq!(pool.run_until(stream.next()), Some((stream.id(), at(1))));
+    assert_eq!(pool.run_until(stream.next()), None);
+}
+
+#[test]
+fn keeps_streaming_after_replacing_the_command() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+
+    let job = Job::cron("*/2 * * * * *").unwrap();
+    let mut stream = pool.run_until(scheduler.stream(job)).unwrap();
+    pool.run_until_stalled();
+
+    pool.run_until(scheduler.replace_command(stream.id(), |_| ()))
+        .unwrap();
+
+    advance(&clock, &mut pool, 4);
+    assert_eq!(received(&mut stream), [2, 4]);
+}
+
+#[test]
+#[should_panic(expected = "stream buffer capacity must be greater than zero")]
+fn rejects_an_empty_buffer() {
+    let (_clock, scheduler, mut pool) = launch(at(0));
+
+    let job = Job::cron("* * * * * *").unwrap();
+    let _stream = pool.run_until(scheduler.stream_with(job, StreamBuffer::DropNewest(0)));
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use async_cron_scheduler::{Clock, Job, MockClock, Scheduler, TimeZoneExt};
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use common::Fired;
+use futures::executor::LocalPool;
+use futures::task::LocalSpawnExt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Runs a job at 09:00 in the given zone for the given number of days, returning when it fired.
+fn nine_am<Tz>(timezone: Tz, from: DateTime<Utc>, days: u32) -> Vec<DateTime<Utc>>
+where
+    Tz: TimeZoneExt + 'static,
+    Tz::Offset: Send + Sync,
+{
+    let clock = MockClock::new(from);
+    let (scheduler, service) = Scheduler::launch(timezone, clock.clone());
+
+    let mut pool = LocalPool::new();
+    pool.spawner().spawn_local(service).unwrap();
+
+    let fired = Fired::default();
+    let job = Job::cron("0 0 9 * * *").unwrap();
+    pool.run_until(scheduler.insert(job, {
+        let (clock, fired) = (clock.clone(), fired.clone());
+        move |_| fired.lock().unwrap().push(clock.now())
+    }))
+    .unwrap();
+    pool.run_until_stalled();
+
+    for _ in 0..days * 24 {
+        clock.advance(Duration::from_secs(3600));
+        pool.run_until_stalled();
+    }
+
+    let fired = fired.lock().unwrap().clone();
+    fired
+}
+
+#[test]
+fn fixed_offset() {
+    let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+    let from = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+
+    assert_eq!(
+        nine_am(tokyo, from, 2),
+        [
+            Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 3, 0, 0, 0).unwrap(),
+        ]
+    );
+}
+
+#[cfg(feature = "chrono-tz")]
+#[test]
+fn named_zone() {
+    use async_cron_scheduler::chrono_tz::America::New_York;
+
+    // Clocks go forward in New York on the 10th of March 2024.
+    let from = Utc.with_ymd_and_hms(2024, 3, 9, 0, 0, 0).unwrap();
+
+    assert_eq!(
+        nine_am(New_York, from, 2),
+        [
+            Utc.with_ymd_and_hms(2024, 3, 9, 14, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 10, 13, 0, 0).unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn per_job_zones() {
+    let utc = FixedOffset::east_opt(0).unwrap();
+    let clock = MockClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap());
+    let (scheduler, service) = Scheduler::launch(utc, clock.clone());
+
+    let mut pool = LocalPool::new();
+    pool.spawner().spawn_local(service).unwrap();
+
+    let fired = Arc::new(Mutex::new(Vec::new()));
+    let zones = [("new york", -5), ("scheduler", 0), ("tokyo", 9)];
+
+    for (name, hours) in zones {
+        let mut job = Job::cron("0 0 9 * * *").unwrap();
+        if name != "scheduler" {
+            job = job.timezone(FixedOffset::east_opt(hours * 3600).unwrap());
+        }
+
+        pool.run_until(scheduler.insert(job, {
+            let (clock, fired) = (clock.clone(), fired.clone());
+            move |_| fired.lock().unwrap().push((name, clock.now()))
+        }))
+        .unwrap();
+    }
+    pool.run_until_stalled();
+
+    for _ in 0..24 {
+        clock.advance(Duration::from_secs(3600));
+        pool.run_until_stalled();
+    }
+
+    let at = |day, hour| Utc.with_ymd_and_hms(2024, 1, day, hour, 0, 0).unwrap();
+    assert_eq!(
+        *fired.lock().unwrap(),
+        [
+            ("new york", at(1, 14)),
+            ("tokyo", at(2, 0)),
+            ("scheduler", at(2, 9)),
+        ]
+    );
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use async_cron_scheduler::{Job, JobId, MockClock, OverlapPolicy, SchedulerEvent};
+use chrono::Utc;
+use common::{at, launch, sleep};
+use futures::executor::LocalPool;
+use futures::stream::Stream;
+use futures::{FutureExt, StreamExt};
+use std::time::Duration;
+
+/// Advances the clock second by second, collecting the events which were delivered.
+fn advance_collecting(
+    clock: &MockClock,
+    pool: &mut LocalPool,
+    events: &mut (impl Stream<Item = SchedulerEvent<Utc>> + Unpin),
+    seconds: u32,
+) -> Vec<SchedulerEvent<Utc>> {
+    let mut drained = Vec::new();
+
+    for second in 0..=seconds {
+        if second > 0 {
+            clock.advance(Duration::from_secs(1));
+        }
+
+        pool.run_until_stalled();
+        while let Some(Some(event)) = events.next().now_or_never() {
+            drained.push(event);
+        }
+    }
+
+    drained
+}
+
+fn fired(id: JobId, second: u32) -> SchedulerEvent<Utc> {
+    SchedulerEvent::Fired {
+        id,
+        scheduled: at(second),
+        actual: at(second),
+    }
+}
+
+#[test]
+fn timed_out() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+    let mut events = pool.run_until(scheduler.subscribe());
+
+    // A hung run would otherwise cause every later occurrence to be skipped.
+    let job = Job::every(Duration::from_secs(60))
+        .overlap(OverlapPolicy::Skip)
+        .timeout(Duration::from_secs(30));
+    let id = pool
+        .run_until(scheduler.insert_async(job, |_| futures::future::pending()))
+        .unwrap();
+
+    assert_eq!(
+        advance_collecting(&clock, &mut pool, &mut events, 29),
+        [SchedulerEvent::Inserted(id), fired(id, 0)]
+    );
+
+    assert_eq!(
+        advance_collecting(&clock, &mut pool, &mut events, 1),
+        [SchedulerEvent::TimedOut(id)]
+    );
+
+    assert_eq!(
+        advance_collecting(&clock, &mut pool, &mut events, 60),
+        [fired(id, 60), SchedulerEvent::TimedOut(id)]
+    );
+}
+
+#[test]
+fn completed_within_timeout() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+    let mut events = pool.run_until(scheduler.subscribe());
+
+    let job = Job::after(Duration::from_secs(0)).timeout(Duration::from_secs(30));
+    let id = pool
+        .run_until(scheduler.insert_async(job, sleep(&clock, 10, |_| (), |_| ())))
+        .unwrap();
+
+    assert_eq!(
+        advance_collecting(&clock, &mut pool, &mut events, 60),
+        [
+            SchedulerEvent::Inserted(id),
+            fired(id, 0),
+            SchedulerEvent::Exhausted(id),
+            SchedulerEvent::Completed(id),
+        ]
+    );
+}
+
+#[test]
+fn cancel_running() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+    let mut events = pool.run_until(scheduler.subscribe());
+
+    let job = Job::every(Duration::from_secs(60)).overlap(OverlapPolicy::Skip);
+    let id = pool
+        .run_until(scheduler.insert_async(job, |_| futures::future::pending()))
+        .unwrap();
+
+    assert_eq!(
+        advance_collecting(&clock, &mut pool, &mut events, 10),
+        [SchedulerEvent::Inserted(id), fired(id, 0)]
+    );
+
+    pool.run_until(scheduler.cancel_running(id)).unwrap();
+    assert_eq!(
+        advance_collecting(&clock, &mut pool, &mut events, 0),
+        [SchedulerEvent::Cancelled(id)]
+    );
+
+    // The next occurrence fires, as the previous run is no longer running.
+    assert_eq!(
+        advance_collecting(&clock, &mut pool, &mut events, 50),
+        [fired(id, 60)]
+    );
+}
+
+#[test]
+fn cancel_running_after_exhausted() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+    let mut events = pool.run_until(scheduler.subscribe());
+
+    let job = Job::after(Duration::from_secs(1));
+    let id = pool
+        .run_until(scheduler.insert_async(job, |_| futures::future::pending()))
+        .unwrap();
+
+    assert_eq!(
+        advance_collecting(&clock, &mut pool, &mut events, 1),
+        [
+            SchedulerEvent::Inserted(id),
+            fired(id, 1),
+            SchedulerEvent::Exhausted(id),
+        ]
+    );
+
+    pool.run_until(scheduler.cancel_running(id)).unwrap();
+    assert_eq!(
+        advance_collecting(&clock, &mut pool, &mut events, 0),
+        [SchedulerEvent::Cancelled(id)]
+    );
+}
+
+#[test]
+fn cancel_running_after_removed() {
+    let (clock, scheduler, mut pool) = launch(at(0));
+    let mut events = pool.run_until(scheduler.subscribe());
+
+    let job = Job::every(Duration::from_secs(60));
+    let id = pool
+        .run_until(scheduler.insert_async(job, |_| futures::future::pending()))
+        .unwrap();
+    advance_collecting(&clock, &mut pool, &mut events, 1);
+
+    pool.run_until(scheduler.remove(id)).unwrap();
+    pool.run_until(scheduler.cancel_running(id)).unwrap();
+    assert_eq!(
+        advance_collecting(&clock, &mut pool, &mut events, 0),
+        [SchedulerEvent::Removed(id), SchedulerEvent::Cancelled(id)]
+    );
+}